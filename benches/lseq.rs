@@ -0,0 +1,140 @@
+//! LSeq benchmarks, run them with `cargo bench --bench lseq`.
+//!
+//! The average identifier depth reached with each bias of the `append_bias` benchmark is printed
+//! by the `lseq_bias` example.
+//!
+//! The `read_small` benchmark needs the `smallvec` feature: `cargo bench --bench lseq --features smallvec`.
+
+use crdts::lseq::LSeq;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn build(n: usize) -> LSeq<u64, u32> {
+    let mut seq = LSeq::new(0);
+    for i in 0..n {
+        seq.append(i as u64);
+    }
+    seq
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let n = 10_000;
+    let mut group = c.benchmark_group("insert");
+    group.sample_size(10);
+
+    group.bench_function(BenchmarkId::new("append", n), |b| {
+        b.iter(|| black_box(build(n)))
+    });
+
+    group.bench_function(BenchmarkId::new("random_index", n), |b| {
+        b.iter(|| {
+            let mut rng = StdRng::seed_from_u64(0);
+            let mut seq = LSeq::new(0u32);
+            for i in 0..n {
+                let ix = rng.gen_range(0, seq.len() + 1);
+                seq.insert_index(ix, i as u64);
+            }
+            black_box(seq)
+        })
+    });
+    group.finish();
+}
+
+fn build_biased(n: usize, bias: f64) -> LSeq<u64, u32> {
+    let mut seq = LSeq::new(0);
+    seq.set_bias(bias);
+    for i in 0..n {
+        seq.append(i as u64);
+    }
+    seq
+}
+
+fn bench_bias(c: &mut Criterion) {
+    let n = 10_000;
+    let mut group = c.benchmark_group("append_bias");
+    group.sample_size(10);
+
+    for bias in [0.5, 0.0].iter() {
+        group.bench_with_input(BenchmarkId::new("bias", bias), bias, |b, bias| {
+            b.iter(|| black_box(build_biased(n, *bias)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_read(c: &mut Criterion) {
+    let n = 10_000;
+    let seq = build(n);
+    let mut group = c.benchmark_group("read");
+
+    group.bench_with_input(BenchmarkId::new("iter_collect", n), &seq, |b, seq| {
+        b.iter(|| black_box(seq.iter().collect::<Vec<_>>()))
+    });
+
+    let columns = seq.to_columnar();
+    group.bench_with_input(
+        BenchmarkId::new("columnar_collect", n),
+        &columns,
+        |b, columns| b.iter(|| black_box(columns.vals.iter().collect::<Vec<_>>())),
+    );
+
+    group.bench_with_input(BenchmarkId::new("clone", n), &seq, |b, seq| {
+        b.iter(|| black_box(seq.clone()))
+    });
+    group.finish();
+}
+
+fn bench_small_read(c: &mut Criterion) {
+    let seq = build(4);
+    let mut group = c.benchmark_group("small_read");
+
+    group.bench_function("collect_vec", |b| {
+        b.iter(|| {
+            black_box(
+                seq.iter_entries()
+                    .map(|e| (&e.id, &e.val))
+                    .collect::<Vec<_>>(),
+            )
+        })
+    });
+
+    #[cfg(feature = "smallvec")]
+    group.bench_function("read_small", |b| b.iter(|| black_box(seq.read_small())));
+
+    group.finish();
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for n in [1_000, 10_000, 100_000].iter() {
+        let seq = build(*n);
+        let ids: Vec<_> = seq.iter_entries().map(|e| e.id.clone()).collect();
+
+        group.bench_with_input(BenchmarkId::new("get", n), &seq, |b, seq| {
+            let mut ix = 0;
+            b.iter(|| {
+                ix = (ix + 7919) % n;
+                black_box(seq.get(ix))
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("position_of", n), &seq, |b, seq| {
+            let mut ix = 0;
+            b.iter(|| {
+                ix = (ix + 7919) % n;
+                black_box(seq.position_of(&ids[ix]))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_insert,
+    bench_bias,
+    bench_read,
+    bench_small_read,
+    bench_lookup
+);
+criterion_main!(benches);
@@ -0,0 +1,125 @@
+use crdts::vclock::{ClockComparer, DenseClock};
+use crdts::{CmRDT, CvRDT, Dot, VClock};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn clocks(n: u64, actors: u64) -> Vec<VClock<u64>> {
+    (0..n)
+        .map(|i| {
+            let mut clock = VClock::new();
+            for actor in 0..actors {
+                clock.apply(Dot::new(actor, (i * 31 + actor * 17) % 100));
+            }
+            clock
+        })
+        .collect()
+}
+
+fn bench_compare(c: &mut Criterion) {
+    let clocks = clocks(16, 64);
+    let mut group = c.benchmark_group("compare_all_pairs_10_rounds");
+
+    group.bench_function("partial_cmp", |b| {
+        b.iter(|| {
+            for _ in 0..10 {
+                for x in clocks.iter() {
+                    for y in clocks.iter() {
+                        black_box(x.partial_cmp(y));
+                    }
+                }
+            }
+        })
+    });
+
+    group.bench_function("clock_comparer", |b| {
+        b.iter(|| {
+            let mut comparer = ClockComparer::new();
+            for _ in 0..10 {
+                for x in clocks.iter() {
+                    for y in clocks.iter() {
+                        black_box(comparer.partial_cmp(x, y));
+                    }
+                }
+            }
+            comparer.comparisons()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_dense(c: &mut Criterion) {
+    let clocks = clocks(2, 4096);
+    let (a, b) = (&clocks[0], &clocks[1]);
+    let (dense_a, dense_b) = (DenseClock::from(a), DenseClock::from(b));
+    let mut group = c.benchmark_group("4096_actors");
+
+    group.bench_function("vclock_merge", |bench| {
+        bench.iter(|| {
+            let mut merged = a.clone();
+            merged.merge(b.clone());
+            merged
+        })
+    });
+
+    group.bench_function("dense_merge", |bench| {
+        bench.iter(|| {
+            let mut merged = dense_a.clone();
+            merged.merge(&dense_b);
+            merged
+        })
+    });
+
+    group.bench_function("vclock_glb", |bench| {
+        bench.iter(|| {
+            let mut glb = a.clone();
+            glb.glb(b);
+            glb
+        })
+    });
+
+    group.bench_function("dense_glb", |bench| {
+        bench.iter(|| {
+            let mut glb = dense_a.clone();
+            glb.glb(&dense_b);
+            glb
+        })
+    });
+
+    group.finish();
+}
+fn bench_dominated_merge(c: &mut Criterion) {
+    let clocks = clocks(2, 4096);
+    let stale = &clocks[1];
+    let mut current = clocks[0].clone();
+    current.merge(stale.clone());
+    let mut group = c.benchmark_group("dominated_merge_4096_actors");
+
+    group.bench_function("merge", |bench| {
+        bench.iter(|| {
+            let mut merged = current.clone();
+            merged.merge(stale.clone());
+            merged
+        })
+    });
+
+    group.bench_function("merge_tracking", |bench| {
+        bench.iter(|| {
+            let mut merged = current.clone();
+            black_box(merged.merge_tracking(stale));
+            merged
+        })
+    });
+
+    group.bench_function("merge_if_newer", |bench| {
+        bench.iter(|| {
+            let mut merged = current.clone();
+            black_box(merged.merge_if_newer(stale));
+            merged
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_compare, bench_dense, bench_dominated_merge);
+criterion_main!(benches);
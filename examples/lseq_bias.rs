@@ -0,0 +1,26 @@
+extern crate crdts;
+
+use crdts::lseq::LSeq;
+
+// Prints the average identifier depth reached by appending with each allocation bias, run it
+// with `cargo run --release --example lseq_bias`.
+fn main() {
+    let n = 10_000;
+    for bias in [None, Some(0.5), Some(0.0)].iter() {
+        let mut seq = LSeq::new(0u32);
+        if let Some(bias) = bias {
+            seq.set_bias(*bias);
+        }
+        for i in 0..n {
+            seq.append(i);
+        }
+
+        let label = bias.map_or("random".to_string(), |b| b.to_string());
+        println!(
+            "average depth of {} appends at bias {}: {:.2}",
+            n,
+            label,
+            seq.average_depth()
+        );
+    }
+}
@@ -1,9 +1,14 @@
 use std::cmp::{Ordering, PartialOrd};
+use std::convert::TryFrom;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::Error;
 use crate::quickcheck::{Arbitrary, Gen};
+use crate::DotStore;
 
 /// Dot is a version marker for a single actor
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,16 +26,18 @@ impl<A: Clone> Dot<A> {
     }
 
     /// Generate the successor of this dot
+    ///
+    /// The counter saturates at `u64::MAX`, a dot already at the maximum is its own successor.
     pub fn inc(&self) -> Self {
         Self {
             actor: self.actor.clone(),
-            counter: self.counter + 1,
+            counter: self.counter.saturating_add(1),
         }
     }
 
-    /// Increment this dot's counter
+    /// Increment this dot's counter, saturating at `u64::MAX` like [`Dot::inc`]
     pub fn apply_inc(&mut self) {
-        self.counter += 1;
+        self.counter = self.counter.saturating_add(1);
     }
 }
 
@@ -61,6 +68,122 @@ impl<A: PartialOrd> PartialOrd for Dot<A> {
     }
 }
 
+impl<A> DotStore<A> for Dot<A> {
+    type Dots = std::iter::Once<Dot<A>>;
+
+    fn iter_dots(self) -> Self::Dots {
+        std::iter::once(self)
+    }
+}
+
+impl<A> DotStore<A> for Vec<Dot<A>> {
+    type Dots = std::vec::IntoIter<Dot<A>>;
+
+    fn iter_dots(self) -> Self::Dots {
+        self.into_iter()
+    }
+}
+
+impl<A: fmt::Display> fmt::Display for Dot<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.actor, self.counter)
+    }
+}
+
+/// Parses the "actor:counter" form written by `Display`.
+///
+/// The counter is taken after the last ':' so actors may themselves contain ':'.
+impl<A: FromStr> FromStr for Dot<A> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (actor, counter) = s.rsplit_once(':').ok_or(Error::InvalidDot)?;
+        Ok(Self {
+            actor: actor.parse().map_err(|_| Error::InvalidDot)?,
+            counter: counter.parse().map_err(|_| Error::InvalidDot)?,
+        })
+    }
+}
+
+/// DotRange is a compact representation of consecutive dots from a single actor.
+///
+/// It covers every counter from `start` to `end` inclusive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DotRange<A> {
+    /// The actor identifier
+    pub actor: A,
+    /// The first counter in the range
+    pub start: u64,
+    /// The last counter in the range, inclusive
+    pub end: u64,
+}
+
+impl<A: Clone> DotRange<A> {
+    /// Build a DotRange covering `start..=end` for the given actor
+    ///
+    /// # Panics
+    ///
+    /// * If `start` is greater than `end`
+    pub fn new(actor: A, start: u64, end: u64) -> Self {
+        assert!(start <= end, "DotRange start must not exceed end");
+        Self { actor, start, end }
+    }
+
+    /// The first dot of this range
+    pub fn first(&self) -> Dot<A> {
+        Dot::new(self.actor.clone(), self.start)
+    }
+
+    /// The last dot of this range
+    pub fn last(&self) -> Dot<A> {
+        Dot::new(self.actor.clone(), self.end)
+    }
+}
+
+/// A range is summarized by its last dot, clocks imply every earlier counter.
+impl<A: Clone> DotStore<A> for DotRange<A> {
+    type Dots = std::iter::Once<Dot<A>>;
+
+    fn iter_dots(self) -> Self::Dots {
+        std::iter::once(self.last())
+    }
+}
+
+impl<A: Clone> From<Dot<A>> for DotRange<A> {
+    fn from(dot: Dot<A>) -> Self {
+        Self::new(dot.actor, dot.counter, dot.counter)
+    }
+}
+
+impl<A: Clone> From<DotRange<A>> for Vec<Dot<A>> {
+    fn from(range: DotRange<A>) -> Self {
+        (range.start..=range.end)
+            .map(|counter| Dot::new(range.actor.clone(), counter))
+            .collect()
+    }
+}
+
+impl<A: Clone + PartialEq> TryFrom<Vec<Dot<A>>> for DotRange<A> {
+    /// The dots are handed back if they can't be represented as a range
+    type Error = Vec<Dot<A>>;
+
+    /// Dots must be non-empty, from a single actor and have consecutive increasing counters
+    fn try_from(dots: Vec<Dot<A>>) -> Result<Self, Self::Error> {
+        let is_range = !dots.is_empty()
+            && dots
+                .windows(2)
+                .all(|w| w[0].actor == w[1].actor && w[0].counter + 1 == w[1].counter);
+
+        if is_range {
+            let first = &dots[0];
+            let last = &dots[dots.len() - 1];
+            Ok(Self::new(first.actor.clone(), first.counter, last.counter))
+        } else {
+            Err(dots)
+        }
+    }
+}
+
 impl<A: Arbitrary + Clone> Arbitrary for Dot<A> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         Dot {
@@ -88,6 +211,18 @@ mod test {
             dot.inc() == Dot::new(dot.actor, dot.counter + 1)
         }
 
+        fn inc_twice_adds_two(dot: Dot<u8>) -> bool {
+            let twice = dot.inc().inc();
+            twice.actor == dot.actor && twice.counter == dot.counter + 2
+        }
+
+        fn display_from_str_round_trip(actor: u64, counter: u64, name: String) -> bool {
+            let numeric = Dot::new(actor, counter);
+            let named = Dot::new(name.replace(':', ""), counter);
+            numeric.to_string().parse() == Ok(numeric)
+                && named.to_string().parse() == Ok(named)
+        }
+
         fn test_partial_order(a: Dot<u8>, b: Dot<u8>) -> bool {
             let cmp_ab = a.partial_cmp(&b);
             let cmp_ba = b.partial_cmp(&a);
@@ -101,4 +236,50 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_dot_display() {
+        assert_eq!(Dot::new(7u64, 3).to_string(), "7:3");
+        assert_eq!(Dot::new("alice".to_string(), 0).to_string(), "alice:0");
+    }
+
+    #[test]
+    fn test_dot_string_round_trip() {
+        let dot = Dot::new("host:1".to_string(), 42);
+        assert_eq!(dot.to_string(), "host:1:42");
+        assert_eq!("host:1:42".parse(), Ok(dot));
+    }
+
+    #[test]
+    fn test_dot_from_malformed_string() {
+        assert_eq!("alice".parse::<Dot<String>>(), Err(Error::InvalidDot));
+        assert_eq!("alice:x".parse::<Dot<String>>(), Err(Error::InvalidDot));
+        assert_eq!("a:-1".parse::<Dot<String>>(), Err(Error::InvalidDot));
+        assert_eq!("x:1".parse::<Dot<u8>>(), Err(Error::InvalidDot));
+    }
+
+    #[test]
+    fn test_dot_range_into_dots() {
+        let range = DotRange::new("A", 3, 5);
+        assert_eq!(range.first(), Dot::new("A", 3));
+        assert_eq!(range.last(), Dot::new("A", 5));
+
+        let dots: Vec<_> = range.clone().into();
+        assert_eq!(
+            dots,
+            vec![Dot::new("A", 3), Dot::new("A", 4), Dot::new("A", 5)]
+        );
+        assert_eq!(DotRange::try_from(dots), Ok(range));
+    }
+
+    #[test]
+    fn test_dot_range_from_non_contiguous_dots() {
+        let gap = vec![Dot::new("A", 1), Dot::new("A", 3)];
+        assert_eq!(DotRange::try_from(gap.clone()), Err(gap));
+
+        let mixed_actors = vec![Dot::new("A", 1), Dot::new("B", 2)];
+        assert_eq!(DotRange::try_from(mixed_actors.clone()), Err(mixed_actors));
+
+        assert_eq!(DotRange::<&str>::try_from(vec![]), Err(vec![]));
+    }
 }
@@ -12,18 +12,33 @@ pub enum Error {
     /// Instead, users must design their system in a way that will make these
     /// dot collisions unlikely / impossible.
     ConflictingMarker,
+
+    /// A string could not be parsed as a `Dot`, expected "actor:counter".
+    InvalidDot,
+
+    /// An op tried to delete an element that is not present.
+    UnknownIdentifier,
+
+    /// An actor's counter is already at `u64::MAX` and can't be incremented.
+    CounterOverflow,
 }
 
 impl error::Error for Error {
     fn description(&self) -> &str {
         match self {
             Error::ConflictingMarker => "Dot's are used exactly once for the lifetime of a CRDT",
+            Error::InvalidDot => "Dot's are written as \"actor:counter\"",
+            Error::UnknownIdentifier => "The identifier is not present",
+            Error::CounterOverflow => "The counter has reached its maximum value",
         }
     }
 
     fn cause(&self) -> Option<&dyn error::Error> {
         match self {
-            Error::ConflictingMarker => None,
+            Error::ConflictingMarker
+            | Error::InvalidDot
+            | Error::UnknownIdentifier
+            | Error::CounterOverflow => None,
         }
     }
 }
@@ -31,7 +46,12 @@ impl error::Error for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::ConflictingMarker => write!(f, "{}", self.to_string()),
+            Error::ConflictingMarker => {
+                write!(f, "Dot's are used exactly once for the lifetime of a CRDT")
+            }
+            Error::InvalidDot => write!(f, "Dot's are written as \"actor:counter\""),
+            Error::UnknownIdentifier => write!(f, "The identifier is not present"),
+            Error::CounterOverflow => write!(f, "The counter has reached its maximum value"),
         }
     }
 }
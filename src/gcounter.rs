@@ -48,7 +48,7 @@ impl<A: Actor> CvRDT for GCounter<A> {
 
 impl<A: Actor> Causal<A> for GCounter<A> {
     fn forget(&mut self, clock: &VClock<A>) {
-        self.inner.forget(&clock);
+        self.inner.forget(clock);
     }
 }
 
@@ -26,6 +26,11 @@
 //! they are delivered in a _causal_ order. Every deletion _must_ be applied _after_ it's
 //! corresponding insertion. To guarantee this property, use a causality barrier.
 //!
+//! Deletions remove the entry from the sequence right away, no tombstone is kept. Identifiers
+//! are totally ordered on their own, so a concurrent insertion next to a deleted element is
+//! still placed correctly without the deleted neighbour being present. As a consequence there
+//! is no tombstone garbage to collect.
+//!
 //! [1] B. Nédelec, P. Molli, A. Mostefaoui, and E. Desmontils,
 //! “LSEQ: an adaptive structure for sequences in distributed collaborative editing,”
 //! in Proceedings of the 2013 ACM symposium on Document engineering - DocEng ’13,
@@ -39,10 +44,33 @@
 /// Contains the implementation of the exponential tree for LSeq
 pub mod ident;
 
+/// Contains an op-based LSeq replica handling causal delivery of ops
+pub mod replica;
+
+/// Contains the streaming serialization of an LSeq
+#[cfg(feature = "streaming")]
+mod stream;
+
+/// Contains a columnar form of an LSeq for fast bulk reads
+pub mod columnar;
+
+/// Contains an LSeq keeping an undo/redo history of its local edits
+pub mod history;
+
+/// Contains the snapshot plus oplog persistence of an LSeq
+#[cfg(feature = "streaming")]
+pub mod persist;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
 use ident::{IdentGen, Identifier};
 use serde::{Deserialize, Serialize};
 
-use crate::{Actor, CmRDT, Dot};
+use crate::ctx::{ReadCtx, RmCtx};
+use crate::{Actor, CmRDT, Dot, Error, VClock};
 
 /// An `Entry` to the LSEQ consists of:
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd)]
@@ -60,14 +88,35 @@ pub struct Entry<T, A: Actor> {
 /// An LSEQ tree is a CRDT for storing sequences of data (Strings, ordered lists).
 /// It provides an efficient view of the stored sequence, with fast index, insertion and deletion
 /// operations.
-#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd)]
+///
+/// The tree is never materialized: elements are kept in a single `Vec` sorted by identifier, so
+/// the tree only exists through the paths of the identifiers. Lookups by identifier are binary
+/// searches and there are no per-node children to store.
+///
+/// Equality, hashing and ordering only look at the state of the sequence: the applied op
+/// counters are left out, so replicas that applied the same ops in a different order, or
+/// received some of them twice, compare as equal.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LSeq<T, A: Actor> {
     seq: Vec<Entry<T, A>>,
     gen: IdentGen<A>,
     dot: Dot<A>,
+    // The fields below were added after the first format, they default when missing so that
+    // LSEQs serialized by older versions still deserialize. Such an LSEQ starts with an empty
+    // clock and zeroed op counters.
+    #[serde(default = "VClock::new")]
+    clock: VClock<A>,
+    #[serde(default)]
+    applied_ops: u64,
+    #[serde(default = "Option::default")]
+    last_applied: Option<Dot<A>>,
 }
 
 /// Operations that can be performed on an LSeq tree
+///
+/// Ops carry the identifier that was allocated when the op was generated, applying an op never
+/// allocates identifiers. Replaying a log of ops is therefore deterministic and places every
+/// element at the exact same identifier on every replica.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd)]
 pub enum Op<T, A: Actor> {
     /// Insert an element
@@ -90,6 +139,25 @@ pub enum Op<T, A: Actor> {
     },
 }
 
+/// A change to the visible sequence, reported by [`LSeq::apply_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeEvent<T> {
+    /// An element was inserted at `index`
+    Inserted {
+        /// Position of the element after the insertion
+        index: usize,
+        /// The inserted element
+        value: T,
+    },
+    /// An element was removed from `index`
+    Removed {
+        /// Position of the element before the removal
+        index: usize,
+        /// The removed element
+        value: T,
+    },
+}
+
 impl<T, A: Actor> Op<T, A> {
     /// Return the Dot originating the operation
     pub fn dot(&self) -> &Dot<A> {
@@ -113,6 +181,9 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
             seq: Vec::new(),
             gen: IdentGen::new(id.clone()),
             dot: Dot::new(id, 0),
+            clock: VClock::new(),
+            applied_ops: 0,
+            last_applied: None,
         }
     }
 
@@ -122,9 +193,41 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
             seq: Vec::new(),
             gen: IdentGen::new_with_args(id.clone(), base, boundary),
             dot: Dot::new(id, 0),
+            clock: VClock::new(),
+            applied_ops: 0,
+            last_applied: None,
         }
     }
 
+    /// Create an empty LSEQ that allocates identifiers deterministically.
+    ///
+    /// Every identifier is allocated at the midpoint of the free interval, so the same local
+    /// edits always produce the same ops. Meant for reproducible tests, see
+    /// [`IdentGen::new_deterministic`].
+    pub fn deterministic(id: A) -> Self {
+        LSeq {
+            seq: Vec::new(),
+            gen: IdentGen::new_deterministic(id.clone()),
+            dot: Dot::new(id, 0),
+            clock: VClock::new(),
+            applied_ops: 0,
+            last_applied: None,
+        }
+    }
+
+    /// Place the identifiers allocated by this LSEQ at a fixed fraction of the allocation
+    /// interval, see [`IdentGen::set_bias`].
+    ///
+    /// Only local insertions are affected, remote ops carry the identifier they were
+    /// allocated with so replicas converge whatever bias each of them uses.
+    ///
+    /// # Panics
+    ///
+    /// * If `bias` is not within `0.0..=1.0`.
+    pub fn set_bias(&mut self, bias: f64) {
+        self.gen.set_bias(bias);
+    }
+
     /// Perform a local insertion of an element at a given position.
     /// If `ix` is greater than the length of the LSeq then it is appended to the end.
     ///
@@ -152,7 +255,7 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
             (prev, next)
         };
 
-        let ix_ident = self.gen.alloc(&lower_id, &upper_id);
+        let ix_ident = self.gen.alloc(lower_id, upper_id);
 
         assert!(lower_id < &ix_ident);
         assert!(&ix_ident < upper_id);
@@ -174,6 +277,46 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
         self.insert_index(ix, c)
     }
 
+    /// Perform a local insertion of `values` as a contiguous block right after `anchor`.
+    ///
+    /// Each value is inserted after the previous one, the block goes to the start of the
+    /// sequence if `anchor` is `None`. If the anchor is no longer in the sequence the block is
+    /// placed where the anchor used to be.
+    pub fn insert_all_after(
+        &mut self,
+        anchor: Option<&Identifier<A>>,
+        values: &[T],
+    ) -> Vec<Op<T, A>> {
+        let start = match anchor {
+            Some(anchor) => match self.seq.binary_search_by(|e| e.id.cmp(anchor)) {
+                Ok(ix) => ix + 1,
+                Err(ix) => ix,
+            },
+            None => 0,
+        };
+
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, val)| self.insert_index(start + i, val.clone()))
+            .collect()
+    }
+
+    /// Perform a local append while keeping at most `cap` elements in the sequence.
+    ///
+    /// If the append takes the sequence over `cap`, the oldest elements by position (the ones at
+    /// the front) are deleted until it fits. The insert is returned first followed by the
+    /// deletes, every op must be delivered to other replicas. Since the evictions are ordinary
+    /// deletes and are chosen strictly by position, replicas converge as long as they all
+    /// receive those ops; replicas should not evict on their own.
+    pub fn push_bounded(&mut self, val: T, cap: usize) -> Vec<Op<T, A>> {
+        let mut ops = vec![self.append(val)];
+        while self.seq.len() > cap {
+            ops.extend(self.delete_index(0));
+        }
+        ops
+    }
+
     /// Perform a local deletion at `ix`.
     ///
     /// If `ix` is out of bounds, i.e. `ix > self.len()`, then
@@ -197,6 +340,30 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
         Some(op)
     }
 
+    /// Perform a local move of the element at `from` so that it ends up at index `to`.
+    ///
+    /// The move is a deletion of the element followed by an insertion of the same value,
+    /// both ops are returned in that order and must be delivered in that order.
+    /// If `from` is out of bounds nothing is done and no ops are returned, if `to` is out of
+    /// bounds the element is moved to the end of the sequence.
+    ///
+    /// Note that a move is not atomic for other replicas: two sites concurrently moving the
+    /// same element will end up with two copies of it, and an element moved while it is being
+    /// concurrently deleted is kept at its new position.
+    pub fn move_to(&mut self, from: usize, to: usize) -> Vec<Op<T, A>> {
+        let val = match self.seq.get(from) {
+            Some(entry) => entry.val.clone(),
+            None => return Vec::new(),
+        };
+
+        let delete = self
+            .delete_index(from)
+            .expect("move_to: 'from' was checked to be in bounds");
+        let insert = self.insert_index(to, val);
+
+        vec![delete, insert]
+    }
+
     /// Perform a local deletion at `ix`. If `ix` is out of bounds
     /// then the last element will be deleted, i.e. `self.len() - 1`.
     pub fn delete_index_or_last(&mut self, ix: usize) -> Op<T, A> {
@@ -208,6 +375,94 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
         }
     }
 
+    /// Derive a remove context scoped to the element with identifier `id`.
+    ///
+    /// The context only holds the dot of the element's insertion, so it can only be used to
+    /// remove that element. Returns `None` if there is no element with this identifier.
+    pub fn rm_ctx_for(&self, id: &Identifier<A>) -> Option<RmCtx<A>> {
+        self.find_entry(id).map(|entry| RmCtx {
+            clock: VClock::from(entry.dot.clone()),
+        })
+    }
+
+    /// Move the element with the longest identifier that can be shortened to a shorter
+    /// identifier at the same position, returning the delete and insert ops of the move.
+    ///
+    /// Elements are tried from the deepest identifier down, `None` is returned once no element
+    /// can be given a shorter identifier. Calling this repeatedly, e.g. when idle, gradually
+    /// undoes the identifier growth left behind by deletions.
+    ///
+    /// This is not coordination-free: a concurrent delete of the moved element is lost and
+    /// concurrent compactions of the same element duplicate it. It should only run on a single
+    /// replica while no other replica is editing.
+    pub fn compact_step(&mut self) -> Option<Vec<Op<T, A>>> {
+        let (min_id, max_id) = (self.gen.lower(), self.gen.upper());
+        let mut by_depth: Vec<usize> = (0..self.seq.len()).collect();
+        by_depth.sort_by_key(|ix| std::cmp::Reverse(self.seq[*ix].id.depth()));
+
+        for ix in by_depth {
+            let depth = self.seq[ix].id.depth();
+            if depth <= 1 {
+                return None;
+            }
+            let seq = &self.seq;
+            let lower = ix.checked_sub(1).map(|i| &seq[i].id).unwrap_or(&min_id);
+            let upper = seq.get(ix + 1).map(|e| &e.id).unwrap_or(&max_id);
+            let id = self.gen.alloc(lower, upper);
+            if id.depth() >= depth {
+                continue;
+            }
+
+            let val = self.seq[ix].val.clone();
+            let delete = self
+                .delete_index(ix)
+                .expect("compact_step: 'ix' is in bounds");
+            self.dot.apply_inc();
+            let insert = Op::Insert {
+                id,
+                dot: self.dot.clone(),
+                val,
+            };
+            self.apply(insert.clone());
+            return Some(vec![delete, insert]);
+        }
+        None
+    }
+
+    /// Perform a local deletion of the element with identifier `id`.
+    ///
+    /// The element is only deleted if `ctx` has seen its insertion, otherwise, or if there is no
+    /// element with this identifier, `None` is returned.
+    pub fn delete_with_ctx(&mut self, id: &Identifier<A>, ctx: RmCtx<A>) -> Option<Op<T, A>> {
+        let ix = self.position_of(id)?;
+        if !ctx.clock.has_seen(&self.seq[ix].dot) {
+            return None;
+        }
+        self.delete_index(ix)
+    }
+
+    /// Split the sequence at the element with identifier `id`, that element and the ones after
+    /// it are moved to the returned LSEQ.
+    ///
+    /// Unlike an index, the identifier marks the same place whatever concurrent edits were
+    /// applied. Both halves keep the actor, identifier generator and clock of this LSEQ, they are
+    /// meant to be used as separate documents from then on: merging ops of one into the other
+    /// mixes two histories made with the same dots.
+    ///
+    /// Fails with [`Error::UnknownIdentifier`] if no element has identifier `id`, leaving this
+    /// LSEQ untouched.
+    pub fn split_at(&mut self, id: &Identifier<A>) -> Result<Self, Error> {
+        let ix = self.position_of(id).ok_or(Error::UnknownIdentifier)?;
+        Ok(LSeq {
+            seq: self.seq.split_off(ix),
+            gen: self.gen.clone(),
+            dot: self.dot.clone(),
+            clock: self.clock.clone(),
+            applied_ops: 0,
+            last_applied: None,
+        })
+    }
+
     /// Get the length of the LSEQ.
     pub fn len(&self) -> usize {
         self.seq.len()
@@ -228,6 +483,40 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
         self.seq.iter()
     }
 
+    /// Iterate over the elements in sequence order along with their identifier and causal
+    /// history.
+    ///
+    /// Elements only keep the dot of their insertion, the clock yielded is the one built from
+    /// that dot, the same one [`LSeq::rm_ctx_for`] uses.
+    pub fn iter_with_clocks(&self) -> impl Iterator<Item = (&Identifier<A>, &T, VClock<A>)> + '_ {
+        self.seq
+            .iter()
+            .map(|entry| (&entry.id, &entry.val, VClock::from(entry.dot.clone())))
+    }
+
+    /// Read the identifiers and values of the LSEQ, keeping sequences of up to 8 elements on the
+    /// stack.
+    ///
+    /// This avoids a heap allocation per read when dealing with lots of short sequences.
+    #[cfg(feature = "smallvec")]
+    pub fn read_small(&self) -> smallvec::SmallVec<[(&Identifier<A>, &T); 8]> {
+        self.seq
+            .iter()
+            .map(|entry| (&entry.id, &entry.val))
+            .collect()
+    }
+
+    /// Iterate over the entries of the LSEQ in batches of `size` entries.
+    ///
+    /// The batches borrow directly from the sequence, the last one may be shorter than `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = &[Entry<T, A>]> + '_ {
+        self.seq.chunks(size)
+    }
+
     /// Get an element at an index from the sequence represented by the LSEQ.
     pub fn get(&self, ix: usize) -> Option<&T> {
         self.seq.get(ix).map(|Entry { val, .. }| val)
@@ -235,7 +524,36 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
 
     /// Finds an entry searching by its Identifier.
     pub fn find_entry(&self, ident: &Identifier<A>) -> Option<&Entry<T, A>> {
-        self.seq.iter().find(|Entry { id, .. }| id == ident)
+        self.position_of(ident).map(|ix| &self.seq[ix])
+    }
+
+    /// Finds the index of an element searching by its Identifier.
+    ///
+    /// Entries are kept sorted by identifier so this is a binary search.
+    pub fn position_of(&self, ident: &Identifier<A>) -> Option<usize> {
+        self.seq.binary_search_by(|e| e.id.cmp(ident)).ok()
+    }
+
+    /// The identifiers bracketing an insertion at `index`: the identifier of the element before
+    /// it and the one of the element currently at `index`.
+    ///
+    /// Either side is `None` at the ends of the sequence, an `index` past the end is treated as
+    /// an append.
+    pub fn neighbors(&self, index: usize) -> (Option<&Identifier<A>>, Option<&Identifier<A>>) {
+        let index = std::cmp::min(index, self.seq.len());
+        let prev = index.checked_sub(1).map(|i| &self.seq[i].id);
+        let next = self.seq.get(index).map(|entry| &entry.id);
+        (prev, next)
+    }
+
+    /// Get first element of the sequence represented by the LSEQ.
+    pub fn first(&self) -> Option<&T> {
+        self.first_entry().map(|Entry { val, .. }| val)
+    }
+
+    /// Get the first Entry of the sequence represented by the LSEQ.
+    pub fn first_entry(&self) -> Option<&Entry<T, A>> {
+        self.seq.first()
     }
 
     /// Get last element of the sequence represented by the LSEQ.
@@ -253,23 +571,395 @@ impl<T: Clone, A: Actor> LSeq<T, A> {
         self.dot.actor.clone()
     }
 
-    /// Insert an identifier and value in the LSEQ
-    fn insert(&mut self, ix: Identifier<A>, dot: Dot<A>, val: T) {
+    /// The clock of every op applied to this LSEQ.
+    pub fn clock(&self) -> &VClock<A> {
+        &self.clock
+    }
+
+    /// The number of ops applied to this LSEQ, local ops included.
+    ///
+    /// Every op handed to `apply` is counted, even the ones that turn out to be no-ops.
+    pub fn applied_op_count(&self) -> u64 {
+        self.applied_ops
+    }
+
+    /// The dot of the last op applied to this LSEQ.
+    pub fn last_applied_dot(&self) -> Option<&Dot<A>> {
+        self.last_applied.as_ref()
+    }
+
+    /// A version number for this LSEQ: the total number of events seen by its clock, saturating
+    /// at `u64::MAX`.
+    ///
+    /// It only grows as ops are applied, so clients can compare it cheaply to know whether the
+    /// document changed. To fetch the changes themselves, hold on to the clock returned by
+    /// [`LSeq::read_since`].
+    pub fn seq_no(&self) -> u64 {
+        self.clock.total_events()
+    }
+
+    /// Returns the insertions of elements that the clock `since` hasn't seen, along with the
+    /// current clock to pass to the next call.
+    ///
+    /// An element is returned if `since` hasn't seen the dot of its insertion, so insertions of
+    /// every writer are returned, whatever the counters of the others. Pass an empty clock to
+    /// read every element.
+    /// Deletions are never returned since no tombstones are kept, clients should fall back to a
+    /// full read if they need an exact view.
+    pub fn read_since(&self, since: &VClock<A>) -> (Vec<Op<T, A>>, VClock<A>) {
+        let ops = self
+            .seq
+            .iter()
+            .filter(|entry| !since.has_seen(&entry.dot))
+            .map(|entry| Op::Insert {
+                id: entry.id.clone(),
+                dot: entry.dot.clone(),
+                val: entry.val.clone(),
+            })
+            .collect();
+        (ops, self.clock.clone())
+    }
+
+    /// The depth of an identifier in the exponential tree, i.e. the length of its path.
+    pub fn depth_of(&self, id: &Identifier<A>) -> usize {
+        id.depth()
+    }
+
+    /// The mean depth of the identifiers of the live elements, `0.0` when the LSEQ is empty.
+    ///
+    /// Identifiers grow as insertions crowd the same region of the tree, a steadily rising
+    /// average is a sign that the workload is bloating identifiers.
+    pub fn average_depth(&self) -> f64 {
+        if self.seq.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.seq.iter().map(|entry| self.depth_of(&entry.id)).sum();
+        total as f64 / self.seq.len() as f64
+    }
+
+    /// Read the sequence in a [`ReadCtx`] whose add and rm clocks are computed separately.
+    ///
+    /// The add clock is the clock of every op applied so far. No tombstones are kept, so the rm
+    /// clock is built from the dots of the elements still present: deletes and the insertions
+    /// they removed don't show up in it. It is the smallest clock from which an [`RmCtx`] for
+    /// any element of the read can be derived.
+    pub fn read_split(&self) -> ReadCtx<Vec<(Identifier<A>, T)>, A> {
+        ReadCtx {
+            add_clock: self.clock.clone(),
+            rm_clock: self.seq.iter().map(|entry| entry.dot.clone()).collect(),
+            val: self
+                .seq
+                .iter()
+                .map(|entry| (entry.id.clone(), entry.val.clone()))
+                .collect(),
+        }
+    }
+
+    /// Clear `buf` and fill it with the identifiers and values of the sequence.
+    ///
+    /// The capacity of `buf` is reused, so reading into the same buffer over and over, e.g.
+    /// once per rendered frame, stops allocating once the buffer is large enough.
+    pub fn read_into(&self, buf: &mut Vec<(Identifier<A>, T)>) {
+        buf.clear();
+        buf.extend(
+            self.seq
+                .iter()
+                .map(|entry| (entry.id.clone(), entry.val.clone())),
+        );
+    }
+
+    /// True if both LSEQs hold equal values in the same order.
+    ///
+    /// Unlike `==`, identifiers, dots and clocks are ignored, so independently built sequences
+    /// with the same content compare as equal.
+    pub fn has_same_values(&self, other: &LSeq<T, A>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().eq(other.iter())
+    }
+
+    /// True if the identifiers along the sequence are strictly increasing.
+    ///
+    /// This is a cheap subset of [`LSeq::validate`] meant for assertions in tests.
+    pub fn is_identifier_sorted(&self) -> bool {
+        self.seq.windows(2).all(|pair| pair[0].id < pair[1].id)
+    }
+
+    /// Check the internal invariants of the LSEQ, this is meant as a debugging aid.
+    ///
+    /// Verifies that identifiers are strictly increasing along the sequence and that every
+    /// identifier falls strictly between the lower and upper bounds of the identifier space.
+    pub fn validate(&self) -> Result<(), String> {
+        let (lower, upper) = (self.gen.lower(), self.gen.upper());
+        for (ix, entry) in self.seq.iter().enumerate() {
+            if entry.id <= lower || entry.id >= upper {
+                return Err(format!("identifier at index {} is out of bounds", ix));
+            }
+        }
+
+        for (ix, pair) in self.seq.windows(2).enumerate() {
+            if pair[0].id >= pair[1].id {
+                return Err(format!(
+                    "identifier at index {} is not greater than the one at index {}",
+                    ix + 1,
+                    ix
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a batch of remote ops in an order that doesn't depend on how they were received.
+    ///
+    /// Inserts are applied first in identifier order, followed by the deletes. Deletes of
+    /// elements that are not in the sequence once the inserts are applied are skipped and
+    /// returned so they can be retried later.
+    pub fn apply_batch(&mut self, ops: Vec<Op<T, A>>) -> Vec<Op<T, A>> {
+        let (mut inserts, deletes): (Vec<_>, Vec<_>) = ops
+            .into_iter()
+            .partition(|op| matches!(op, Op::Insert { .. }));
+        inserts.sort_by(|a, b| a.id().cmp(b.id()));
+        for op in inserts {
+            self.apply(op);
+        }
+
+        let mut skipped = Vec::new();
+        for op in deletes {
+            let present = match &op {
+                Op::Delete { id, remote, .. } => self
+                    .find_entry(id)
+                    .map(|entry| &entry.dot == remote)
+                    .unwrap_or(false),
+                Op::Insert { .. } => unreachable!("inserts were partitioned out"),
+            };
+            if present {
+                self.apply(op);
+            } else {
+                skipped.push(op);
+            }
+        }
+        skipped
+    }
+
+    /// Merge two op logs into a single log without duplicates, in causal order.
+    ///
+    /// Ops are identified by their dot, an op present in both logs is kept once. In the merged
+    /// log every op comes after the previous op of its actor and a delete comes after the
+    /// insert it removes, when that insert is part of either log. Among the ops that are free to
+    /// go next the one with the lowest counter is picked, ties are broken by actor, so the
+    /// result doesn't depend on the order of the logs.
+    pub fn merge_ops(a: Vec<Op<T, A>>, b: Vec<Op<T, A>>) -> Vec<Op<T, A>> {
+        let mut ops: HashMap<Dot<A>, Op<T, A>> = HashMap::new();
+        for op in a.into_iter().chain(b) {
+            ops.entry(op.dot().clone()).or_insert(op);
+        }
+
+        let mut dependents: HashMap<Dot<A>, Vec<Dot<A>>> = HashMap::new();
+        let mut waiting_on: HashMap<Dot<A>, usize> = HashMap::new();
+        let mut add_dependency = |before: Dot<A>, after: Dot<A>| {
+            dependents.entry(before).or_default().push(after.clone());
+            *waiting_on.entry(after).or_default() += 1;
+        };
+
+        let mut counters: BTreeMap<A, Vec<u64>> = BTreeMap::new();
+        for dot in ops.keys() {
+            counters
+                .entry(dot.actor.clone())
+                .or_default()
+                .push(dot.counter);
+        }
+        for (actor, counters) in counters.iter_mut() {
+            counters.sort_unstable();
+            for pair in counters.windows(2) {
+                add_dependency(
+                    Dot::new(actor.clone(), pair[0]),
+                    Dot::new(actor.clone(), pair[1]),
+                );
+            }
+        }
+        for (dot, op) in ops.iter() {
+            if let Op::Delete { remote, .. } = op {
+                if ops.contains_key(remote) {
+                    add_dependency(remote.clone(), dot.clone());
+                }
+            }
+        }
+
+        let mut ready: BTreeSet<(u64, A)> = ops
+            .keys()
+            .filter(|dot| !waiting_on.contains_key(dot))
+            .map(|dot| (dot.counter, dot.actor.clone()))
+            .collect();
+        let mut merged = Vec::with_capacity(ops.len());
+        while let Some((counter, actor)) = ready.iter().next().cloned() {
+            ready.remove(&(counter, actor.clone()));
+            let dot = Dot::new(actor, counter);
+            for next in dependents.remove(&dot).unwrap_or_default() {
+                let waiting = waiting_on.get_mut(&next).expect("dependents are waiting");
+                *waiting -= 1;
+                if *waiting == 0 {
+                    waiting_on.remove(&next);
+                    ready.insert((next.counter, next.actor));
+                }
+            }
+            merged.extend(ops.remove(&dot));
+        }
+
+        // Only a malformed log, such as a delete of an insert its author made later, leaves ops
+        // behind. They are appended by counter rather than dropped.
+        let mut rest: Vec<_> = ops.into_iter().collect();
+        rest.sort_by(|(a, _), (b, _)| (a.counter, &a.actor).cmp(&(b.counter, &b.actor)));
+        merged.extend(rest.into_iter().map(|(_, op)| op));
+        merged
+    }
+
+    /// Apply an op received from another replica.
+    ///
+    /// This is `apply`, which never allocates identifiers: an insert carries the identifier its
+    /// author allocated with [`LSeq::insert_index`] and friends, and the RNG is only used there.
+    /// Replicas applying the same remote ops therefore build identical sequences.
+    pub fn apply_remote(&mut self, op: Op<T, A>) {
+        self.apply(op);
+    }
+
+    /// Apply an operation, failing on deletes of elements that are not present.
+    ///
+    /// Unlike `apply`, which ignores them, a delete whose identifier is not in the sequence or
+    /// belongs to a different insertion returns `Error::UnknownIdentifier` and is not applied.
+    /// This can surface bugs or out of order delivery.
+    pub fn apply_checked(&mut self, op: Op<T, A>) -> Result<(), Error> {
+        if let Op::Delete { id, remote, .. } = &op {
+            match self.find_entry(id) {
+                Some(entry) if &entry.dot == remote => (),
+                _ => return Err(Error::UnknownIdentifier),
+            }
+        }
+        self.apply(op);
+        Ok(())
+    }
+
+    /// Apply an operation only if every dot it carries is permitted by the trust `frontier`.
+    ///
+    /// This is meant for ops received from untrusted peers: an op whose dot, or for deletes the
+    /// dot of the insertion it removes, claims an event the frontier has not seen is dropped
+    /// instead of being applied. Returns `true` if the op was applied.
+    pub fn apply_verified(&mut self, op: Op<T, A>, frontier: &VClock<A>) -> bool {
+        let trusted = match &op {
+            Op::Insert { dot, .. } => frontier.has_seen(dot),
+            Op::Delete { remote, dot, .. } => frontier.has_seen(remote) && frontier.has_seen(dot),
+        };
+        if trusted {
+            self.apply(op);
+        }
+        trusted
+    }
+
+    /// Apply an operation, calling `on_change` for every change it makes to the sequence.
+    ///
+    /// This follows the same semantics as `apply`, ops that turn out to be no-ops
+    /// do not produce an event.
+    pub fn apply_with<F: FnMut(ChangeEvent<T>)>(&mut self, op: Op<T, A>, mut on_change: F) {
+        self.record_applied(op.dot());
+        match op {
+            Op::Insert { id, dot, val } => {
+                if let Some(index) = self.insert(id, dot, val) {
+                    let value = self.seq[index].val.clone();
+                    on_change(ChangeEvent::Inserted { index, value });
+                }
+            }
+            Op::Delete { id, remote, .. } => {
+                if let Some((index, entry)) = self.delete(id, &remote) {
+                    on_change(ChangeEvent::Removed {
+                        index,
+                        value: entry.val,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Record that the op with this dot is being applied
+    fn record_applied(&mut self, dot: &Dot<A>) {
+        self.clock.apply(dot.clone());
+        self.applied_ops += 1;
+        self.last_applied = Some(dot.clone());
+    }
+
+    /// Insert an identifier and value in the LSEQ, returning the index it was inserted at
+    fn insert(&mut self, ix: Identifier<A>, dot: Dot<A>, val: T) -> Option<usize> {
         // Inserts only have an impact if the identifier is not in the tree
-        if let Err(res) = self.seq.binary_search_by(|e| e.id.cmp(&ix)) {
-            self.seq.insert(res, Entry { id: ix, dot, val });
+        match self.seq.binary_search_by(|e| e.id.cmp(&ix)) {
+            Ok(_) => None,
+            Err(res) => {
+                self.seq.insert(res, Entry { id: ix, dot, val });
+                Some(res)
+            }
         }
     }
 
-    /// Remove an identifier from the LSEQ
-    fn delete(&mut self, ix: Identifier<A>) {
-        // Deletes only have an effect if the identifier is already in the tree
-        if let Ok(i) = self.seq.binary_search_by(|e| e.id.cmp(&ix)) {
-            self.seq.remove(i);
+    /// Remove an identifier from the LSEQ, returning the removed entry and its former index
+    fn delete(&mut self, ix: Identifier<A>, remote: &Dot<A>) -> Option<(usize, Entry<T, A>)> {
+        // Deletes only have an effect if the identifier is already in the tree and was inserted
+        // by the op we are deleting, the identifier may have been freed and allocated again.
+        match self.seq.binary_search_by(|e| e.id.cmp(&ix)) {
+            Ok(i) if &self.seq[i].dot == remote => Some((i, self.seq.remove(i))),
+            _ => None,
         }
     }
 }
 
+impl<T: Display, A: Actor> LSeq<T, A> {
+    /// Render the sequence by concatenating the `Display` output of its elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::lseq::LSeq;
+    /// let mut seq = LSeq::new("A");
+    /// seq.append('h');
+    /// seq.append('i');
+    /// assert_eq!(seq.to_display_string(), "hi");
+    /// ```
+    pub fn to_display_string(&self) -> String {
+        self.seq
+            .iter()
+            .map(|Entry { val, .. }| val.to_string())
+            .collect()
+    }
+}
+
+impl<T: PartialEq, A: Actor> PartialEq for LSeq<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+            && self.gen == other.gen
+            && self.dot == other.dot
+            && self.clock == other.clock
+    }
+}
+
+impl<T: Eq, A: Actor> Eq for LSeq<T, A> {}
+
+impl<T: Hash, A: Actor> Hash for LSeq<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.seq.hash(state);
+        self.gen.hash(state);
+        self.dot.hash(state);
+        self.clock.hash(state);
+    }
+}
+
+impl<T: PartialOrd, A: Actor> PartialOrd for LSeq<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (&self.seq, &self.gen, &self.dot, &self.clock).partial_cmp(&(
+            &other.seq,
+            &other.gen,
+            &other.dot,
+            &other.clock,
+        ))
+    }
+}
+
 impl<T: Clone, A: Actor> CmRDT for LSeq<T, A> {
     type Op = Op<T, A>;
     /// Apply an operation to an LSeq instance.
@@ -277,12 +967,53 @@ impl<T: Clone, A: Actor> CmRDT for LSeq<T, A> {
     /// If the operation is an insert and the identifier is **already** present in the LSEQ instance
     /// the result is a no-op
     ///
-    /// If the operation is a delete and the identifier is **not** present in the LSEQ instance the
-    /// result is a no-op
+    /// If the operation is a delete and the identifier is **not** present in the LSEQ instance, or
+    /// is present but was inserted by a different op than the one being deleted, the result is a
+    /// no-op
     fn apply(&mut self, op: Self::Op) {
+        self.record_applied(op.dot());
         match op {
-            Op::Insert { id, dot, val } => self.insert(id, dot, val),
-            Op::Delete { id, .. } => self.delete(id),
+            Op::Insert { id, dot, val } => {
+                self.insert(id, dot, val);
+            }
+            Op::Delete { id, remote, .. } => {
+                self.delete(id, &remote);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_detects_unordered_identifiers() {
+        let mut site = LSeq::new(0u8);
+        site.append('a');
+        site.append('b');
+        site.append('c');
+        assert_eq!(site.validate(), Ok(()));
+
+        assert!(site.is_identifier_sorted());
+
+        site.seq.swap(1, 2);
+        assert!(!site.is_identifier_sorted());
+        assert_eq!(
+            site.validate(),
+            Err("identifier at index 2 is not greater than the one at index 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_detects_out_of_bounds_identifiers() {
+        let mut site = LSeq::new(0u8);
+        site.append('a');
+
+        site.seq[0].id = site.gen.upper();
+        assert_eq!(
+            site.validate(),
+            Err("identifier at index 0 is out of bounds".to_string())
+        );
+    }
+}
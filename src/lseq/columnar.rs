@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use super::ident::{IdentGen, Identifier};
+use super::{Entry, LSeq};
+use crate::{Actor, Dot, VClock};
+
+/// A columnar form of an LSeq, storing identifiers, dots and values in parallel arrays.
+///
+/// Reading every value out of this form walks a single contiguous array of values, which is
+/// friendlier to the cache than walking the entries. This suits read mostly workloads such as
+/// rendering a static document. Use [`LSeq::to_columnar`] to build one and
+/// [`LSeq::from_columnar`] to get back the LSeq.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Columns<T, A: Actor> {
+    /// The identifier of every element, in order
+    pub ids: Vec<Identifier<A>>,
+    /// The dot of the insertion of every element, in order
+    pub dots: Vec<Dot<A>>,
+    /// The elements' values, in order
+    pub vals: Vec<T>,
+    gen: IdentGen<A>,
+    dot: Dot<A>,
+    #[serde(default = "VClock::new")]
+    clock: VClock<A>,
+    #[serde(default)]
+    applied_ops: u64,
+    #[serde(default = "Option::default")]
+    last_applied: Option<Dot<A>>,
+}
+
+impl<T: Clone, A: Actor> LSeq<T, A> {
+    /// Build the columnar form of this LSEQ.
+    pub fn to_columnar(&self) -> Columns<T, A> {
+        let mut columns = Columns {
+            ids: Vec::with_capacity(self.seq.len()),
+            dots: Vec::with_capacity(self.seq.len()),
+            vals: Vec::with_capacity(self.seq.len()),
+            gen: self.gen.clone(),
+            dot: self.dot.clone(),
+            clock: self.clock.clone(),
+            applied_ops: self.applied_ops,
+            last_applied: self.last_applied.clone(),
+        };
+        for entry in self.seq.iter() {
+            columns.ids.push(entry.id.clone());
+            columns.dots.push(entry.dot.clone());
+            columns.vals.push(entry.val.clone());
+        }
+        columns
+    }
+
+    /// Rebuild an LSEQ from its columnar form.
+    ///
+    /// Fails if the columns have different lengths or if they don't form a valid LSEQ,
+    /// see [`LSeq::validate`].
+    pub fn from_columnar(columns: Columns<T, A>) -> Result<Self, String> {
+        let len = columns.vals.len();
+        if columns.ids.len() != len || columns.dots.len() != len {
+            return Err("columns have different lengths".to_string());
+        }
+
+        let seq = columns
+            .ids
+            .into_iter()
+            .zip(columns.dots)
+            .zip(columns.vals)
+            .map(|((id, dot), val)| Entry { id, dot, val })
+            .collect();
+
+        let lseq = Self {
+            seq,
+            gen: columns.gen,
+            dot: columns.dot,
+            clock: columns.clock,
+            applied_ops: columns.applied_ops,
+            last_applied: columns.last_applied,
+        };
+        lseq.validate()?;
+        Ok(lseq)
+    }
+}
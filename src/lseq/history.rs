@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::ident::Identifier;
+use super::{LSeq, Op};
+use crate::{Actor, CmRDT, Dot};
+
+/// A local edit, sufficient to compute the edit undoing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum Edit<T, A: Actor> {
+    Inserted { id: Identifier<A>, dot: Dot<A> },
+    Deleted { id: Identifier<A>, val: T },
+}
+
+/// An LSEQ keeping a bounded undo/redo history of its local edits.
+///
+/// Local edits made through this wrapper are recorded, ops applied from other replicas are
+/// not. Undoing an insert deletes the inserted element, undoing a delete inserts the value again
+/// at the place it was deleted from. Undo and redo produce ordinary ops that must be delivered
+/// to other replicas like any other local op.
+///
+/// Since other replicas keep editing concurrently, undo is best effort: undoing an insert of an
+/// element that has since been deleted does nothing, and a value restored by undoing a delete
+/// is a new element, so concurrent edits relative to the deleted one are not carried over to it.
+///
+/// # Examples
+/// ```
+/// use crdts::lseq::history::History;
+///
+/// let mut doc = History::new("A", 10);
+/// doc.insert_index(0, 'a');
+/// doc.insert_index(1, 'b');
+///
+/// doc.undo();
+/// assert_eq!(doc.lseq().iter().collect::<String>(), "a");
+///
+/// doc.redo();
+/// assert_eq!(doc.lseq().iter().collect::<String>(), "ab");
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct History<T, A: Actor> {
+    seq: LSeq<T, A>,
+    cap: usize,
+    undo: VecDeque<Edit<T, A>>,
+    redo: Vec<Edit<T, A>>,
+}
+
+impl<T: Clone, A: Actor> History<T, A> {
+    /// Create an empty LSEQ remembering at most `cap` local edits
+    pub fn new(actor: A, cap: usize) -> Self {
+        History {
+            seq: LSeq::new(actor),
+            cap,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// The LSEQ holding the document
+    pub fn lseq(&self) -> &LSeq<T, A> {
+        &self.seq
+    }
+
+    /// Perform a local insertion, see [`LSeq::insert_index`].
+    pub fn insert_index(&mut self, ix: usize, val: T) -> Op<T, A> {
+        let op = self.seq.insert_index(ix, val);
+        self.record(Edit::Inserted {
+            id: op.id().clone(),
+            dot: op.dot().clone(),
+        });
+        op
+    }
+
+    /// Perform a local deletion, see [`LSeq::delete_index`].
+    pub fn delete_index(&mut self, ix: usize) -> Option<Op<T, A>> {
+        let val = self.seq.get(ix)?.clone();
+        let op = self.seq.delete_index(ix)?;
+        self.record(Edit::Deleted {
+            id: op.id().clone(),
+            val,
+        });
+        Some(op)
+    }
+
+    /// Undo the last local edit, returning the ops performing the undo.
+    ///
+    /// Returns `None` if there is nothing to undo. The returned ops may be empty if the edit
+    /// was already undone by a concurrent edit.
+    pub fn undo(&mut self) -> Option<Vec<Op<T, A>>> {
+        let edit = self.undo.pop_back()?;
+        let inverted = self.invert(edit).map(|(op, inverse)| {
+            self.redo.push(inverse);
+            op
+        });
+        Some(inverted.into_iter().collect())
+    }
+
+    /// Redo the last undone edit, returning the ops performing the redo.
+    ///
+    /// Returns `None` if there is nothing to redo. Any new local edit clears the redo history.
+    pub fn redo(&mut self) -> Option<Vec<Op<T, A>>> {
+        let edit = self.redo.pop()?;
+        let inverted = self.invert(edit).map(|(op, inverse)| {
+            self.undo.push_back(inverse);
+            op
+        });
+        Some(inverted.into_iter().collect())
+    }
+
+    fn record(&mut self, edit: Edit<T, A>) {
+        self.redo.clear();
+        self.undo.push_back(edit);
+        if self.undo.len() > self.cap {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Perform the inverse of `edit`, returning its op and the edit that would revert it.
+    fn invert(&mut self, edit: Edit<T, A>) -> Option<(Op<T, A>, Edit<T, A>)> {
+        match edit {
+            Edit::Inserted { id, dot } => {
+                let ix = match self.seq.position_of(&id) {
+                    Some(ix) if self.seq.seq[ix].dot == dot => ix,
+                    _ => return None,
+                };
+                let val = self.seq.seq[ix].val.clone();
+                let op = self
+                    .seq
+                    .delete_index(ix)
+                    .expect("ix was checked to be in bounds");
+                Some((op, Edit::Deleted { id, val }))
+            }
+            Edit::Deleted { id, val } => {
+                let ix = match self.seq.seq.binary_search_by(|e| e.id.cmp(&id)) {
+                    Ok(ix) | Err(ix) => ix,
+                };
+                let op = self.seq.insert_index(ix, val);
+                let inverse = Edit::Inserted {
+                    id: op.id().clone(),
+                    dot: op.dot().clone(),
+                };
+                Some((op, inverse))
+            }
+        }
+    }
+}
+
+impl<T: Clone, A: Actor> CmRDT for History<T, A> {
+    type Op = Op<T, A>;
+
+    /// Apply an op from another replica, it is not recorded in the history.
+    fn apply(&mut self, op: Self::Op) {
+        self.seq.apply(op);
+    }
+}
@@ -8,6 +8,19 @@ const DEFAULT_BOUNDARY: u64 = 10;
 
 const DEFAULT_INITIAL_BASE: u8 = 3; // start with 2^3
 
+/// The index stored at each level of an identifier path.
+///
+/// Enabling the `u32-ids` feature stores these as `u32` instead of `u64`, roughly halving the
+/// memory of identifier paths and making cloning them cheaper. Arities are capped at 2^30 so
+/// both widths can address every node of the tree: the number of elements an LSeq can hold is
+/// limited by the maximum depth of the tree, not by this type.
+#[cfg(not(feature = "u32-ids"))]
+pub type Segment = u64;
+
+/// The index stored at each level of an identifier path, `u32` under the `u32-ids` feature.
+#[cfg(feature = "u32-ids")]
+pub type Segment = u32;
+
 /// A tree identifier uniquely locates an element in an LSeq tree.
 /// It represents the path that needs to be taken in order to reach
 /// the element. At each level we store the index of the child tree node
@@ -15,7 +28,14 @@ const DEFAULT_INITIAL_BASE: u8 = 3; // start with 2^3
 /// two sites decide to pick the same child index to allocate a fresh node
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Clone, Serialize, Deserialize, Hash)]
 pub struct Identifier<A: Actor> {
-    path: Vec<(u64, Option<A>)>,
+    path: Vec<(Segment, Option<A>)>,
+}
+
+impl<A: Actor> Identifier<A> {
+    /// The number of levels of the tree this identifier goes through.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
 }
 
 /// A generator for fresh identifiers.
@@ -35,6 +55,13 @@ pub struct IdentGen<A: Actor> {
     /// We keep a cache of the strategy chosen for each level of the tree
     #[serde(skip)] // TODO: implement serialize/deserialize
     strategy_vec: BitVec,
+    /// Always allocate at the midpoint of the free interval instead of a random boundary offset
+    #[serde(default)]
+    deterministic: bool,
+    /// Where in the boundary interval fresh indices are placed, in ten-thousandths of the
+    /// interval. `None` places them uniformly at random.
+    #[serde(default)]
+    bias: Option<u16>,
     /// Site id of the trees generated by this generator
     pub site_id: A,
 }
@@ -51,10 +78,44 @@ impl<A: Actor> IdentGen<A> {
             initial_base_bits: base,
             boundary,
             strategy_vec: BitVec::new(),
+            deterministic: false,
+            bias: None,
             site_id,
         }
     }
 
+    /// Create a generator that always allocates at the midpoint of the free interval.
+    ///
+    /// Identifiers then only depend on where the insertions happen, which makes them
+    /// reproducible. This is meant for tests, the allocations it makes are far less compact than
+    /// the randomized default.
+    pub fn new_deterministic(site_id: A) -> Self {
+        IdentGen {
+            deterministic: true,
+            ..Self::new(site_id)
+        }
+    }
+
+    /// Place fresh indices at a fixed fraction of the boundary interval instead of at random.
+    ///
+    /// A `bias` of `0.0` allocates right next to the reference identifier of the level's
+    /// strategy, `1.0` as far from it as the boundary allows and `0.5` in the middle, which is
+    /// where the random default lands on average. Hugging the reference identifier leaves more
+    /// room for the following insertions in workloads that keep inserting on the same side, such
+    /// as appends. The bias is kept with a precision of `1/10000`.
+    ///
+    /// # Panics
+    ///
+    /// * If `bias` is not within `0.0..=1.0`.
+    pub fn set_bias(&mut self, bias: f64) {
+        assert!(
+            (0.0..=1.0).contains(&bias),
+            "bias must be within 0.0..=1.0, got {}",
+            bias
+        );
+        self.bias = Some((bias * 10_000.0).round() as u16);
+    }
+
     /// The smallest possible node in a tree.
     pub fn lower(&self) -> Identifier<A> {
         Identifier {
@@ -197,30 +258,30 @@ impl<A: Actor> IdentGen<A> {
         z
     }
 
-    fn replace_last(&mut self, p: &Identifier<A>, depth: usize, ix: u64) -> Identifier<A> {
+    fn replace_last(&mut self, p: &Identifier<A>, depth: usize, ix: Segment) -> Identifier<A> {
         let mut ident = p.clone();
         ident.path.truncate(depth);
         ident.path.push((ix, Some(self.site_id.clone())));
         ident
     }
 
-    fn push_index(&mut self, p: &Identifier<A>, ix: u64) -> Identifier<A> {
+    fn push_index(&mut self, p: &Identifier<A>, ix: Segment) -> Identifier<A> {
         let mut ident = p.clone();
         ident.path.push((ix, Some(self.site_id.clone())));
         ident
     }
 
-    fn arity_at(&self, depth: usize) -> u64 {
+    fn arity_at(&self, depth: usize) -> Segment {
         let base_bits = (self.initial_base_bits as u32) + (depth as u32);
         assert!(base_bits < 31, "maximum depth exceeded");
 
-        2u64.pow(base_bits)
+        Segment::pow(2, base_bits)
     }
     // Generate an index in a given range at the specified depth.
     // Uses the allocation strategy of that depth, boundary+ or boundary- which is biased to the
     // lower and upper ends of the range respectively.
     // should allocate in the range [lower, upper)
-    fn index_in_range(&mut self, lower: u64, upper: u64, depth: usize) -> u64 {
+    fn index_in_range(&mut self, lower: Segment, upper: Segment, depth: usize) -> Segment {
         assert!(
             lower < upper,
             "need at least one space between the bounds lower={} upper={}",
@@ -228,13 +289,20 @@ impl<A: Actor> IdentGen<A> {
             upper
         );
 
-        let mut rng = rand::rngs::OsRng;
-        let interval = std::cmp::min(self.boundary, upper - lower);
+        if self.deterministic {
+            return lower + (upper - lower) / 2;
+        }
 
-        let step = if interval > 0 {
-            rng.gen_range(0, interval)
-        } else {
-            0
+        let mut rng = rand::rngs::OsRng;
+        // the range is at most the arity of a level so it always fits in a `Segment`,
+        // the cast is only a no-op when `Segment` is `u64`
+        #[allow(clippy::unnecessary_cast)]
+        let interval = std::cmp::min(self.boundary, (upper - lower) as u64) as Segment;
+
+        let step = match self.bias {
+            _ if interval == 0 => 0,
+            Some(bias) => ((interval - 1) as f64 * f64::from(bias) / 10_000.0).round() as Segment,
+            None => rng.gen_range(0, interval),
         };
 
         let index = if self.strategy(depth) {
@@ -271,7 +339,8 @@ impl<A: quickcheck::Arbitrary + Actor> quickcheck::Arbitrary for Identifier<A> {
         let chosen_depth = u8::arbitrary(g) % (max_depth - min_depth) + min_depth;
         let mut path = Vec::new();
         for depth in 0..chosen_depth {
-            let i = u64::arbitrary(g) % (2u64.pow((DEFAULT_INITIAL_BASE + depth).into()) + 1);
+            let i = Segment::arbitrary(g)
+                % (Segment::pow(2, (DEFAULT_INITIAL_BASE + depth).into()) + 1);
             path.push((i, Option::arbitrary(g)));
         }
 
@@ -279,7 +348,8 @@ impl<A: quickcheck::Arbitrary + Actor> quickcheck::Arbitrary for Identifier<A> {
         // anything before this identifier
         if path.last().unwrap().0 == 0 {
             path.pop();
-            let i = 1 + u64::arbitrary(g) % 2u64.pow((DEFAULT_INITIAL_BASE + chosen_depth).into());
+            let i = 1 + Segment::arbitrary(g)
+                % Segment::pow(2, (DEFAULT_INITIAL_BASE + chosen_depth).into());
             assert_ne!(i, 0);
             path.push((i, Option::arbitrary(g)));
         };
@@ -425,6 +495,59 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_deterministic_alloc() {
+        let mut gen = IdentGen::new_deterministic(0);
+        let mut p = gen.lower();
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            p = gen.alloc(&p, &gen.upper());
+            ids.push(p.path.clone());
+        }
+
+        assert_eq!(
+            ids,
+            vec![
+                vec![(4, Some(0))],
+                vec![(6, Some(0))],
+                vec![(7, Some(0))],
+                vec![(7, Some(0)), (8, Some(0))],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alloc_between_adjacent_siblings() {
+        let mut gen = IdentGen::new(0);
+
+        let p = Identifier {
+            path: vec![(3, Some(1)), (5, Some(1))],
+        };
+        let q = Identifier {
+            path: vec![(3, Some(1)), (6, Some(1))],
+        };
+
+        let z = gen.alloc(&p, &q);
+        assert!(p < z && z < q);
+        assert_eq!(z.path.len(), 3);
+        assert_eq!(&z.path[..2], p.path.as_slice());
+    }
+
+    #[test]
+    fn test_alloc_before_first_element() {
+        let mut gen = IdentGen::new(0);
+        let lower = gen.lower();
+        let mut q = Identifier {
+            path: vec![(1, Some(1))],
+        };
+
+        for _ in 0..10 {
+            let z = gen.alloc(&lower, &q);
+            assert!(lower < z && z < q);
+            q = z;
+        }
+    }
+
     #[test]
     fn test_index_in_range() {
         let mut gen = IdentGen::new(0);
@@ -450,4 +573,38 @@ mod test {
         assert!(z < b);
         assert_eq!(&z.path[0..1], a.path.as_slice());
     }
+
+    #[test]
+    fn test_biased_alloc_is_not_random() {
+        // only the strategy picked for the level is random, each gives a single index
+        let indices: std::collections::BTreeSet<_> = (0..32)
+            .map(|_| {
+                let mut gen = IdentGen::new(0);
+                gen.set_bias(0.0);
+                gen.alloc(&gen.lower(), &gen.upper()).path[0].0
+            })
+            .collect();
+        assert!(indices.len() <= 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "bias must be within 0.0..=1.0")]
+    fn test_bias_out_of_range() {
+        IdentGen::new(0).set_bias(1.5);
+    }
+
+    #[cfg(feature = "u32-ids")]
+    #[test]
+    fn test_u32_segments() {
+        assert_eq!(std::mem::size_of::<Segment>(), 4);
+
+        let mut gen = IdentGen::new(0);
+        let mut upper = gen.upper();
+        let lower = gen.lower();
+        for _ in 0..(31 - DEFAULT_INITIAL_BASE as usize) {
+            let z = gen.alloc(&lower, &upper);
+            assert!(lower < z && z < upper);
+            upper = z;
+        }
+    }
 }
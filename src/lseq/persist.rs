@@ -0,0 +1,144 @@
+//! Persistence of an LSeq as a snapshot followed by a log of the ops applied since.
+//!
+//! Replaying every op of a large document is slow and a snapshot alone loses the updates made
+//! after it was taken. Instead, a snapshot is written every now and then with [`write_snapshot`]
+//! and every op applied afterwards, local or remote, is appended to an oplog with
+//! [`append_op`]. [`load`] reads the snapshot back and replays the oplog on top of it.
+//!
+//! Compaction is done by writing a fresh snapshot of the current state and then truncating the
+//! oplog. Ops the snapshot has already seen are skipped by [`load`], so a crash between the two
+//! steps leaves a pair that still loads to the right state.
+//!
+//! Both the snapshot and the oplog use the record format of
+//! [`LSeq::serialize_streaming`].
+//!
+//! This needs the `streaming` feature.
+//!
+//! # Examples
+//! ```
+//! use crdts::lseq::{persist, LSeq};
+//!
+//! let mut seq = LSeq::new(0u8);
+//! seq.append('a');
+//! let mut snapshot = Vec::new();
+//! persist::write_snapshot(&seq, &mut snapshot).unwrap();
+//!
+//! let mut oplog = Vec::new();
+//! persist::append_op(&mut oplog, &seq.append('b')).unwrap();
+//!
+//! let loaded = persist::load::<char, u8, _, _>(&mut snapshot.as_slice(), &mut oplog.as_slice()).unwrap();
+//! assert_eq!(loaded.iter().collect::<String>(), "ab");
+//! ```
+
+use std::io::{self, Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::stream::{read_record_or_eof, write_record};
+use super::{LSeq, Op};
+use crate::{Actor, CmRDT};
+
+/// Write a snapshot of `lseq` to `writer`.
+///
+/// The oplog paired with the previous snapshot can be truncated once this returns.
+pub fn write_snapshot<T: Serialize, A: Actor + Serialize, W: Write>(
+    lseq: &LSeq<T, A>,
+    writer: &mut W,
+) -> io::Result<()> {
+    lseq.serialize_streaming(writer)
+}
+
+/// Append `op` to the oplog written to `writer`.
+pub fn append_op<T: Serialize, A: Actor + Serialize, W: Write>(
+    writer: &mut W,
+    op: &Op<T, A>,
+) -> io::Result<()> {
+    write_record(writer, op)
+}
+
+/// Load an LSEQ from a snapshot and the oplog written after it.
+///
+/// Ops of the oplog that the snapshot has already seen are skipped. The local dot is moved past
+/// the replayed local ops so that fresh ops don't reuse their dots.
+///
+/// Fails with [`io::ErrorKind::UnexpectedEof`] if the oplog ends in the middle of an op, and
+/// with [`io::ErrorKind::InvalidData`] if the snapshot or an op can't be decoded.
+pub fn load<T, A, S, L>(snapshot: &mut S, oplog: &mut L) -> io::Result<LSeq<T, A>>
+where
+    T: Clone + DeserializeOwned,
+    A: Actor + DeserializeOwned,
+    S: Read,
+    L: Read,
+{
+    let mut lseq = LSeq::deserialize_streaming(snapshot)?;
+    while let Some(op) = read_record_or_eof::<_, Op<T, A>>(oplog)? {
+        if !lseq.clock.has_seen(op.dot()) {
+            lseq.apply(op);
+        }
+    }
+    lseq.dot.counter = std::cmp::max(lseq.dot.counter, lseq.clock.get(&lseq.dot.actor));
+    Ok(lseq)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_snapshot_and_oplog() {
+        let mut site1 = LSeq::new(0u8);
+        let mut site2 = LSeq::new(1u8);
+        site2.apply(site1.append('a'));
+        site2.apply(site1.append('b'));
+
+        let mut snapshot = Vec::new();
+        write_snapshot(&site1, &mut snapshot).unwrap();
+
+        let mut oplog = Vec::new();
+        let remote = site2.insert_index(0, 'x');
+        site1.apply(remote.clone());
+        append_op(&mut oplog, &remote).unwrap();
+        append_op(&mut oplog, &site1.append('c')).unwrap();
+        append_op(&mut oplog, &site1.delete_index(1).unwrap()).unwrap();
+
+        let mut loaded: LSeq<char, u8> =
+            load(&mut snapshot.as_slice(), &mut oplog.as_slice()).unwrap();
+        assert_eq!(loaded.iter().collect::<String>(), "xbc");
+        assert_eq!(loaded.clock(), site1.clock());
+
+        // fresh local ops don't reuse the dots of the replayed ones
+        assert_eq!(loaded.append('d').dot(), site1.append('d').dot());
+    }
+
+    #[test]
+    fn test_load_skips_ops_seen_by_snapshot() {
+        let mut seq = LSeq::new(0u8);
+        let mut oplog = Vec::new();
+        append_op(&mut oplog, &seq.append('a')).unwrap();
+        append_op(&mut oplog, &seq.append('b')).unwrap();
+
+        // compaction interrupted before the oplog was truncated
+        let mut snapshot = Vec::new();
+        write_snapshot(&seq, &mut snapshot).unwrap();
+
+        let loaded: LSeq<char, u8> = load(&mut snapshot.as_slice(), &mut oplog.as_slice()).unwrap();
+        assert_eq!(loaded.iter().collect::<String>(), "ab");
+        assert_eq!(loaded.applied_op_count(), seq.applied_op_count());
+    }
+
+    #[test]
+    fn test_load_truncated_oplog() {
+        let mut seq = LSeq::new(0u8);
+        let mut snapshot = Vec::new();
+        write_snapshot(&seq, &mut snapshot).unwrap();
+
+        let mut oplog = Vec::new();
+        append_op(&mut oplog, &seq.append('a')).unwrap();
+        oplog.pop();
+
+        let err = load::<char, u8, _, _>(&mut snapshot.as_slice(), &mut oplog.as_slice())
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{LSeq, Op};
+use crate::{Actor, CmRDT, VClock};
+
+/// An op along with the clock of the replica that produced it, at the time it was produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaOp<T, A: Actor> {
+    /// Every op this op causally depends on
+    pub clock: VClock<A>,
+    /// The LSEQ op
+    pub op: Op<T, A>,
+}
+
+/// An op-based LSEQ replica that takes care of causal delivery.
+///
+/// Local edits are queued up as outgoing ops, remote ops are buffered until every op they
+/// causally depend on has been applied. Since identifiers of deleted elements may be
+/// allocated again, LSEQ needs this full causal order and not only deletes being applied after
+/// their insertions. Ops that have already been applied are ignored so the same op may be
+/// received more than once.
+///
+/// # Examples
+/// ```
+/// use crdts::lseq::replica::Replica;
+///
+/// let mut a = Replica::new("A");
+/// let mut b = Replica::new("B");
+///
+/// a.local_insert(0, 'x');
+/// a.local_insert(1, 'y');
+///
+/// // deliver the ops out of order, `b` waits for the first one before applying the second
+/// let ops = a.outgoing_ops();
+/// b.receive(ops.into_iter().rev());
+///
+/// assert_eq!(b.lseq().iter().collect::<String>(), "xy");
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replica<T, A: Actor> {
+    seq: LSeq<T, A>,
+    /// Ops waiting on their dependencies, by author and then by counter
+    pending: BTreeMap<A, BTreeMap<u64, ReplicaOp<T, A>>>,
+    outbox: Vec<ReplicaOp<T, A>>,
+}
+
+impl<T: Clone, A: Actor> Replica<T, A> {
+    /// Create a replica with an empty LSEQ
+    pub fn new(actor: A) -> Self {
+        Replica {
+            seq: LSeq::new(actor),
+            pending: BTreeMap::new(),
+            outbox: Vec::new(),
+        }
+    }
+
+    /// Actor who is initiating operations on this replica
+    pub fn actor(&self) -> A {
+        self.seq.actor()
+    }
+
+    /// The LSEQ held by this replica
+    pub fn lseq(&self) -> &LSeq<T, A> {
+        &self.seq
+    }
+
+    /// The clock of every op applied to this replica, i.e. the clock of its LSEQ
+    pub fn clock(&self) -> &VClock<A> {
+        self.seq.clock()
+    }
+
+    /// Received ops that are still waiting on their causal dependencies, ordered by author and
+    /// then by counter
+    pub fn pending_ops(&self) -> impl Iterator<Item = &ReplicaOp<T, A>> {
+        self.pending.values().flat_map(|ops| ops.values())
+    }
+
+    /// Insert an element at the given position, queueing the op for delivery to other replicas.
+    pub fn local_insert(&mut self, ix: usize, val: T) {
+        let clock = self.clock().clone();
+        let op = self.seq.insert_index(ix, val);
+        self.outbox.push(ReplicaOp { clock, op });
+    }
+
+    /// Delete the element at the given position, queueing the op for delivery to other
+    /// replicas. Returns false if `ix` was out of bounds and nothing was deleted.
+    pub fn local_delete(&mut self, ix: usize) -> bool {
+        let clock = self.clock().clone();
+        match self.seq.delete_index(ix) {
+            Some(op) => {
+                self.outbox.push(ReplicaOp { clock, op });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take the ops produced locally since the last call.
+    pub fn outgoing_ops(&mut self) -> Vec<ReplicaOp<T, A>> {
+        std::mem::take(&mut self.outbox)
+    }
+
+    /// Receive ops from other replicas, applying every op that is causally ready.
+    pub fn receive<I: IntoIterator<Item = ReplicaOp<T, A>>>(&mut self, ops: I) {
+        for op in ops {
+            if !self.clock().has_seen(op.op.dot()) {
+                let dot = op.op.dot().clone();
+                self.pending
+                    .entry(dot.actor)
+                    .or_default()
+                    .entry(dot.counter)
+                    .or_insert(op);
+            }
+        }
+
+        // The only op of an author that can be ready is the one following the last applied op
+        // of that author. Keep applying those until none of them is ready.
+        let mut progress = true;
+        while progress {
+            progress = false;
+            let authors: Vec<A> = self.pending.keys().cloned().collect();
+            for author in authors {
+                while self.apply_next_of(&author) {
+                    progress = true;
+                }
+            }
+        }
+    }
+
+    /// Apply the next pending op of `author` if it is causally ready.
+    fn apply_next_of(&mut self, author: &A) -> bool {
+        let next = match self.clock().get(author).checked_add(1) {
+            Some(next) => next,
+            None => return false,
+        };
+        let ops = match self.pending.get_mut(author) {
+            Some(ops) => ops,
+            None => return false,
+        };
+        let clock = self.seq.clock();
+        let ready = ops
+            .get(&next)
+            .map(|op| op.clock.dots.iter().all(|(a, c)| clock.get(a) >= *c))
+            .unwrap_or(false);
+        if !ready {
+            return false;
+        }
+
+        let ReplicaOp { op, .. } = ops.remove(&next).unwrap();
+        if ops.is_empty() {
+            self.pending.remove(author);
+        }
+        self.seq.apply(op);
+        true
+    }
+}
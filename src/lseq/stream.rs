@@ -0,0 +1,103 @@
+//! Streaming serialization of an LSeq.
+//!
+//! The stream starts with a header record holding the identifier generator, the local dot, the
+//! clock, the applied op counters and the number of entries, followed by one record per entry. Every record is encoded
+//! with bincode and prefixed by its length as a little-endian `u64`, so documents can be written
+//! and read one element at a time without holding the whole encoding in memory.
+//!
+//! This needs the `streaming` feature.
+
+use std::io::{self, Read, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{ident::IdentGen, LSeq};
+use crate::{Actor, Dot, VClock};
+
+type Header<A> = (IdentGen<A>, Dot<A>, VClock<A>, u64, Option<Dot<A>>, u64);
+
+impl<T: Serialize, A: Actor + Serialize> LSeq<T, A> {
+    /// Write this LSEQ to `writer`, one entry at a time.
+    pub fn serialize_streaming<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header = (
+            &self.gen,
+            &self.dot,
+            &self.clock,
+            self.applied_ops,
+            &self.last_applied,
+            self.seq.len() as u64,
+        );
+        write_record(writer, &header)?;
+        for entry in self.seq.iter() {
+            write_record(writer, entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone + DeserializeOwned, A: Actor + DeserializeOwned> LSeq<T, A> {
+    /// Read an LSEQ written by [`LSeq::serialize_streaming`] from `reader`.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if a record can't be decoded or if the decoded
+    /// entries don't form a valid LSEQ.
+    pub fn deserialize_streaming<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let (gen, dot, clock, applied_ops, last_applied, len): Header<A> = read_record(reader)?;
+
+        let mut seq = Vec::new();
+        for _ in 0..len {
+            seq.push(read_record(reader)?);
+        }
+
+        let lseq = Self {
+            seq,
+            gen,
+            dot,
+            clock,
+            applied_ops,
+            last_applied,
+        };
+        lseq.validate()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(lseq)
+    }
+}
+
+pub(super) fn write_record<W: Write, S: Serialize>(writer: &mut W, record: &S) -> io::Result<()> {
+    let bytes = bincode::serialize(record)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)
+}
+
+fn read_record<R: Read, D: DeserializeOwned>(reader: &mut R) -> io::Result<D> {
+    read_record_or_eof(reader)?.ok_or_else(|| io::ErrorKind::UnexpectedEof.into())
+}
+
+/// Reads the next record, or `None` if `reader` ends right before it.
+pub(super) fn read_record_or_eof<R: Read, D: DeserializeOwned>(
+    reader: &mut R,
+) -> io::Result<Option<D>> {
+    let mut len = [0u8; 8];
+    let mut filled = 0;
+    while filled < len.len() {
+        match reader.read(&mut len[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    let len = u64::from_le_bytes(len);
+
+    // read through `take` rather than allocating `len` bytes up front, the length is untrusted
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    bincode::deserialize(&bytes)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
@@ -1,6 +1,5 @@
 use std::cmp::Ordering;
 use std::fmt::{self, Display};
-use std::mem;
 
 use serde::{Deserialize, Serialize};
 
@@ -91,7 +90,7 @@ impl<V: Clone, A: Actor> Causal<A> for MVReg<V, A> {
             .clone()
             .into_iter()
             .filter_map(|(mut val_clock, val)| {
-                val_clock.forget(&clock);
+                val_clock.forget(clock);
                 if val_clock.is_empty() {
                     None // remove this value from the register
                 } else {
@@ -110,7 +109,7 @@ impl<V, A: Actor> Default for MVReg<V, A> {
 
 impl<V, A: Actor> CvRDT for MVReg<V, A> {
     fn merge(&mut self, other: Self) {
-        self.vals = mem::replace(&mut self.vals, Vec::new())
+        self.vals = std::mem::take(&mut self.vals)
             .into_iter()
             .filter(|(clock, _)| other.vals.iter().filter(|(c, _)| clock < c).count() == 0)
             .collect();
@@ -136,11 +135,12 @@ impl<V, A: Actor> CmRDT for MVReg<V, A> {
                     return;
                 }
                 // first filter out all values that are dominated by the Op clock
-                self.vals
-                    .retain(|(val_clock, _)| match val_clock.partial_cmp(&clock) {
-                        None | Some(Ordering::Greater) => true,
-                        _ => false,
-                    });
+                self.vals.retain(|(val_clock, _)| {
+                    matches!(
+                        val_clock.partial_cmp(&clock),
+                        None | Some(Ordering::Greater)
+                    )
+                });
 
                 // TAI: in the case were the Op has a context that already was present,
                 //      the above line would remove that value, the next lines would
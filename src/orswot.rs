@@ -2,7 +2,6 @@
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::mem;
 
 use serde::{Deserialize, Serialize};
 
@@ -80,7 +79,7 @@ impl<M: Member, A: Actor> CmRDT for Orswot<M, A> {
 impl<M: Member, A: Actor> CvRDT for Orswot<M, A> {
     /// Merge combines another `Orswot` with this one.
     fn merge(&mut self, other: Self) {
-        self.entries = mem::replace(&mut self.entries, HashMap::new())
+        self.entries = std::mem::take(&mut self.entries)
             .into_iter()
             .filter_map(|(entry, mut clock)| {
                 if !other.entries.contains_key(&entry) {
@@ -109,7 +108,7 @@ impl<M: Member, A: Actor> CvRDT for Orswot<M, A> {
                 // SUBTLE: this entry is present in both orswots, BUT that doesn't mean we
                 // shouldn't drop it!
                 // Perfectly possible that an item in both sets should be dropped
-                let mut common = VClock::intersection(&clock, &our_clock);
+                let mut common = VClock::intersection(&clock, our_clock);
                 common.merge(clock.clone_without(&self.clock));
                 common.merge(our_clock.clone_without(&other.clock));
                 if common.is_empty() {
@@ -149,14 +148,14 @@ impl<M: Member, A: Actor> CvRDT for Orswot<M, A> {
 
 impl<M: Member, A: Actor> Causal<A> for Orswot<M, A> {
     fn forget(&mut self, clock: &VClock<A>) {
-        self.clock.forget(&clock);
+        self.clock.forget(clock);
 
         self.entries = self
             .entries
             .clone()
             .into_iter()
             .filter_map(|(val, mut val_clock)| {
-                val_clock.forget(&clock);
+                val_clock.forget(clock);
                 if val_clock.is_empty() {
                     None
                 } else {
@@ -170,7 +169,7 @@ impl<M: Member, A: Actor> Causal<A> for Orswot<M, A> {
             .clone()
             .into_iter()
             .filter_map(|(mut vclock, deferred)| {
-                vclock.forget(&clock);
+                vclock.forget(clock);
                 if vclock.is_empty() {
                     None
                 } else {
@@ -231,10 +230,10 @@ impl<M: Member, A: Actor> Orswot<M, A> {
     /// Remove members using a witnessing clock.
     fn apply_rm(&mut self, members: HashSet<M>, clock: VClock<A>) {
         for member in members.iter() {
-            if let Some(member_clock) = self.entries.get_mut(&member) {
+            if let Some(member_clock) = self.entries.get_mut(member) {
                 member_clock.forget(&clock);
                 if member_clock.is_empty() {
-                    self.entries.remove(&member);
+                    self.entries.remove(member);
                 }
             }
         }
@@ -253,7 +252,7 @@ impl<M: Member, A: Actor> Orswot<M, A> {
 
     /// Check if the set contains a member
     pub fn contains(&self, member: &M) -> ReadCtx<bool, A> {
-        let member_clock_opt = self.entries.get(&member);
+        let member_clock_opt = self.entries.get(member);
         let exists = member_clock_opt.is_some();
         ReadCtx {
             add_clock: self.clock.clone(),
@@ -281,7 +280,7 @@ impl<M: Member, A: Actor> Orswot<M, A> {
     }
 
     fn apply_deferred(&mut self) {
-        let deferred = mem::replace(&mut self.deferred, HashMap::new());
+        let deferred = std::mem::take(&mut self.deferred);
         for (clock, entries) in deferred.into_iter() {
             self.apply_rm(entries, clock)
         }
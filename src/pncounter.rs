@@ -75,8 +75,8 @@ impl<A: Actor> CvRDT for PNCounter<A> {
 
 impl<A: Actor> Causal<A> for PNCounter<A> {
     fn forget(&mut self, clock: &VClock<A>) {
-        self.p.forget(&clock);
-        self.n.forget(&clock);
+        self.p.forget(clock);
+        self.n.forget(clock);
     }
 }
 
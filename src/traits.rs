@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use crate::VClock;
+use crate::{Dot, VClock};
 
 /// Common Actor type. Actors are unique identifier for every `thing` mutating a VClock.
 /// VClock based CRDT's will need to expose this Actor type to the user.
@@ -48,6 +48,15 @@ pub trait Causal<A: Actor> {
     fn forget(&mut self, clock: &VClock<A>);
 }
 
+/// A source of dots that can be merged into a `VClock`, see [`VClock::merge`].
+pub trait DotStore<A> {
+    /// The iterator returned by `iter_dots`
+    type Dots: Iterator<Item = Dot<A>>;
+
+    /// Consume the store, returning the dots it holds
+    fn iter_dots(self) -> Self::Dots;
+}
+
 /// Funky variant of the `CvRDT` trait.
 ///
 /// This trait is for CvRDT's whose state space can't be easily encoded in rusts
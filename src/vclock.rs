@@ -13,15 +13,31 @@
 //! ```
 
 use std::cmp::{self, Ordering};
-use std::collections::{btree_map, BTreeMap};
+use std::collections::{btree_map, BTreeMap, BTreeSet, HashMap};
+use std::convert::TryFrom;
 use std::fmt::{self, Display};
 use std::hash::Hash;
-use std::mem;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, RangeBounds, Sub};
 
 use serde::{Deserialize, Serialize};
 
+use crate::dot::DotRange;
 use crate::quickcheck::{Arbitrary, Gen};
-use crate::{Actor, Causal, CmRDT, CvRDT, Dot};
+use crate::{Actor, Causal, CmRDT, CvRDT, Dot, DotStore, Error};
+
+/// How two clocks relate causally, see [`VClock::relation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// Both clocks have seen the same events
+    Equal,
+    /// The clock has seen every event of the other one and more
+    Dominates,
+    /// The other clock has seen every event of this one and more
+    Dominated,
+    /// Each clock has seen events the other hasn't
+    Concurrent,
+}
 
 /// A `VClock` is a standard vector clock.
 /// It contains a set of "actors" and associated counters.
@@ -32,6 +48,12 @@ use crate::{Actor, Causal, CmRDT, CvRDT, Dot};
 /// It can tell you if something causally descends something else,
 /// or if different replicas are "concurrent" (were mutated in
 /// isolation, and need to be resolved externally).
+///
+/// `VClock` implements `PartialOrd` as the causal order and deliberately doesn't implement
+/// `Ord`, which would have to agree with `partial_cmp` on every pair, concurrent clocks
+/// included. Where a total order is needed, e.g. to key a `BTreeMap`, order by
+/// [`VClock::to_sorted_vec`] or the `dots` map and keep in mind that this order says nothing
+/// about causality.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct VClock<A: Actor> {
     /// dots is the mapping from actors to their associated counters
@@ -46,25 +68,46 @@ impl<A: Actor> Default for VClock<A> {
 
 impl<A: Actor> PartialOrd for VClock<A> {
     fn partial_cmp(&self, other: &VClock<A>) -> Option<Ordering> {
-        // This algorithm is pretty naive, I think there's a way to
-        // just track if the ordering changes as we iterate over the
-        // active dots zipped by actor.
-        // ie. it's None if the ordering changes from Less to Greator
-        //     or vice-versa.
-
-        if self == other {
-            Some(Ordering::Equal)
-        } else if other.dots.iter().all(|(w, c)| self.get(w) >= *c) {
-            Some(Ordering::Greater)
-        } else if self.dots.iter().all(|(w, c)| other.get(w) >= *c) {
-            Some(Ordering::Less)
-        } else {
-            None
+        // Walk the actors of both clocks in order at once, noting on which sides a counter is
+        // ahead. An actor missing from a clock has an implied counter of 0.
+        let mut self_ahead = false;
+        let mut other_ahead = false;
+        let mut mine = self.dots.iter().peekable();
+        let mut theirs = other.dots.iter().peekable();
+        loop {
+            let next = match (mine.peek(), theirs.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((a, _)), Some((b, _))) => a.cmp(b),
+            };
+            let (mine_counter, their_counter) = match next {
+                Ordering::Less => (mine.next().map(|(_, c)| *c).unwrap_or(0), 0),
+                Ordering::Greater => (0, theirs.next().map(|(_, c)| *c).unwrap_or(0)),
+                Ordering::Equal => (
+                    mine.next().map(|(_, c)| *c).unwrap_or(0),
+                    theirs.next().map(|(_, c)| *c).unwrap_or(0),
+                ),
+            };
+            self_ahead |= mine_counter > their_counter;
+            other_ahead |= their_counter > mine_counter;
+            if self_ahead && other_ahead {
+                return None;
+            }
+        }
+
+        match (self_ahead, other_ahead) {
+            (false, false) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Greater),
+            (false, true) => Some(Ordering::Less),
+            (true, true) => None,
         }
     }
 }
 
 impl<A: Actor + Display> Display for VClock<A> {
+    /// Formats the clock as `<A:3, B:7>`, actors in ascending order and `<>` for the empty
+    /// clock. This format is stable and may be relied on, e.g. in snapshot tests.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "<")?;
         for (i, (actor, count)) in self.dots.iter().enumerate() {
@@ -77,13 +120,61 @@ impl<A: Actor + Display> Display for VClock<A> {
     }
 }
 
+impl<A: Actor + Display> VClock<A> {
+    /// A human readable report of how this clock relates to `other`, meant for debugging.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{Dot, VClock};
+    /// let a: VClock<_> = vec![Dot::new("A", 3), Dot::new("B", 1)].into_iter().collect();
+    /// let b: VClock<_> = vec![Dot::new("A", 1), Dot::new("B", 2)].into_iter().collect();
+    /// assert_eq!(
+    ///     a.diff_summary(&b),
+    ///     "self leads on A by 2, behind on B by 1; relation: concurrent"
+    /// );
+    /// ```
+    pub fn diff_summary(&self, other: &VClock<A>) -> String {
+        let actors: BTreeSet<&A> = self.dots.keys().chain(other.dots.keys()).collect();
+        let mut parts = Vec::new();
+        for (i, actor) in actors.into_iter().enumerate() {
+            let (ours, theirs) = (self.get(actor), other.get(actor));
+            let part = match ours.cmp(&theirs) {
+                Ordering::Greater => format!("leads on {} by {}", actor, ours - theirs),
+                Ordering::Less => format!("behind on {} by {}", actor, theirs - ours),
+                Ordering::Equal => format!("equal on {}", actor),
+            };
+            if i == 0 {
+                parts.push(format!("self {}", part));
+            } else {
+                parts.push(part);
+            }
+        }
+
+        let relation = match self.relation(other) {
+            Relation::Dominates => "ahead",
+            Relation::Dominated => "behind",
+            Relation::Equal => "equal",
+            Relation::Concurrent => "concurrent",
+        };
+
+        if parts.is_empty() {
+            format!("relation: {}", relation)
+        } else {
+            format!("{}; relation: {}", parts.join(", "), relation)
+        }
+    }
+}
+
 impl<A: Actor> Causal<A> for VClock<A> {
-    /// Forget any actors that have smaller counts than the
-    /// count in the given vclock
+    /// Forget any actors that have smaller or equal counts than the
+    /// count in the given vclock.
+    ///
+    /// This is the reset-remove of op-based CRDTs: the history `other` has seen is dropped,
+    /// actors this vclock has seen more of are left untouched.
     fn forget(&mut self, other: &Self) {
         for Dot { actor, counter } in other.iter() {
-            if counter >= self.get(&actor) {
-                self.dots.remove(&actor);
+            if counter >= self.get(actor) {
+                self.dots.remove(actor);
             }
         }
     }
@@ -108,18 +199,59 @@ impl<A: Actor> CmRDT for VClock<A> {
     /// assert_eq!(v.get(&"A"), 2);
     /// ```
     fn apply(&mut self, dot: Self::Op) {
-        self.apply_dot(dot);
+        self.merge_dot(dot);
+    }
+}
+
+impl<A: Actor> DotStore<A> for VClock<A> {
+    type Dots = IntoIter<A>;
+
+    fn iter_dots(self) -> Self::Dots {
+        self.into_iter()
     }
 }
 
 impl<A: Actor> CvRDT for VClock<A> {
     fn merge(&mut self, other: Self) {
-        for dot in other.into_iter() {
-            self.apply_dot(dot);
+        *self += &other;
+    }
+}
+
+impl<A: Actor> AddAssign<&VClock<A>> for VClock<A> {
+    /// Merges `other` into this clock, `a += &b` is `a.merge(b.clone())` without the clone.
+    fn add_assign(&mut self, other: &VClock<A>) {
+        for (actor, counter) in other.dots.iter() {
+            self.merge_dot(Dot::new(actor.clone(), *counter));
         }
     }
 }
 
+impl<A: Actor> AddAssign<Dot<A>> for VClock<A> {
+    /// Applies `dot`, `clock += dot` is `clock.apply(dot)`.
+    fn add_assign(&mut self, dot: Dot<A>) {
+        self.merge_dot(dot);
+    }
+}
+
+impl<A: Actor> Add<Dot<A>> for VClock<A> {
+    type Output = VClock<A>;
+
+    /// Returns this clock with `dot` applied.
+    fn add(mut self, dot: Dot<A>) -> Self::Output {
+        self += dot;
+        self
+    }
+}
+
+impl<A: Actor> Sub<&VClock<A>> for &VClock<A> {
+    type Output = VClock<A>;
+
+    /// Returns the dots of this clock that `other` hasn't seen, see [`VClock::diff`].
+    fn sub(self, other: &VClock<A>) -> Self::Output {
+        self.diff(other)
+    }
+}
+
 impl<A: Actor> VClock<A> {
     /// Returns a new `VClock` instance.
     pub fn new() -> Self {
@@ -128,21 +260,169 @@ impl<A: Actor> VClock<A> {
         }
     }
 
+    /// Build a clock from `(actor, counter)` pairs, keeping the largest counter of each actor.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::VClock;
+    /// let clock = VClock::from_slice(&[("A", 4), ("B", 3), ("A", 1)]);
+    /// assert_eq!(clock.get(&"A"), 4);
+    /// assert_eq!(clock.get(&"B"), 3);
+    /// ```
+    pub fn from_slice(dots: &[(A, u64)]) -> Self {
+        dots.iter()
+            .map(|(actor, counter)| Dot::new(actor.clone(), *counter))
+            .collect()
+    }
+
     /// Returns a clone of self but with information that is older than given clock is
     /// forgotten
     pub fn clone_without(&self, base_clock: &Self) -> Self {
         let mut cloned = self.clone();
-        cloned.forget(&base_clock);
+        cloned.forget(base_clock);
         cloned
     }
 
-    /// Apply a Dot to this vclock.
-    fn apply_dot(&mut self, dot: Dot<A>) {
+    /// Returns the dots of this clock that `other` hasn't seen, `&a - &b` is `a.diff(&b)`.
+    ///
+    /// Merging the result into `other` catches it up with this clock.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::VClock;
+    /// let a = VClock::from_slice(&[("A", 3), ("B", 1), ("C", 2)]);
+    /// let b = VClock::from_slice(&[("A", 1), ("B", 4)]);
+    /// assert_eq!(a.diff(&b), VClock::from_slice(&[("A", 3), ("C", 2)]));
+    /// assert_eq!(&a - &b, a.diff(&b));
+    /// ```
+    pub fn diff(&self, other: &Self) -> Self {
+        self.clone_without(other)
+    }
+
+    /// Merge any source of dots into this vclock, keeping the largest counter of each actor.
+    ///
+    /// This accepts another `VClock` as well as a single `Dot`, a `Vec<Dot>` or a `DotRange`.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{dot::DotRange, Dot, VClock};
+    /// let mut clock = VClock::new();
+    /// clock.merge_from(Dot::new("A", 2));
+    /// clock.merge_from(vec![Dot::new("A", 1), Dot::new("B", 1)]);
+    /// clock.merge_from(DotRange::new("B", 2, 4));
+    /// assert_eq!(clock.get(&"A"), 2);
+    /// assert_eq!(clock.get(&"B"), 4);
+    /// ```
+    pub fn merge_from<S: DotStore<A>>(&mut self, store: S) {
+        for dot in store.iter_dots() {
+            self.merge_dot(dot);
+        }
+    }
+
+    /// Replace the contents of this vclock with those of `other`, actors missing from `other`
+    /// are dropped.
+    ///
+    /// Unlike merging this can move causality backward, forgetting events this vclock has
+    /// seen. Only use it where that is intended, e.g. when installing a snapshot.
+    pub fn set_to(&mut self, other: &VClock<A>) {
+        self.dots.clone_from(&other.dots);
+    }
+
+    /// Consuming form of [`VClock::merge`], handy when folding clocks.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::VClock;
+    /// let clocks = vec![VClock::from_slice(&[("A", 2)]), VClock::from_slice(&[("B", 1)])];
+    /// let lub = clocks.into_iter().fold(VClock::new(), VClock::merged);
+    /// assert_eq!(lub, VClock::from_slice(&[("A", 2), ("B", 1)]));
+    /// ```
+    pub fn merged(mut self, other: VClock<A>) -> Self {
+        self.merge(other);
+        self
+    }
+
+    /// Merge a single Dot into this vclock.
+    ///
+    /// This is equivalent to merging `VClock::from(dot)` without building the
+    /// intermediate clock.
+    pub fn merge_dot(&mut self, dot: Dot<A>) {
         if self.get(&dot.actor) < dot.counter {
             self.dots.insert(dot.actor, dot.counter);
         }
     }
 
+    /// Advance `actor` to `counter`, unless the clock has already seen that counter.
+    ///
+    /// On conflict the clock is left untouched and the dot it already holds for the actor is
+    /// returned, so callers can report which event made this one stale.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{Dot, VClock};
+    /// let mut clock = VClock::new();
+    /// assert_eq!(clock.try_witness("A", 2), Ok(()));
+    /// assert_eq!(clock.try_witness("A", 1), Err(Dot::new("A", 2)));
+    /// ```
+    pub fn try_witness(&mut self, actor: A, counter: u64) -> Result<(), Dot<A>> {
+        let current = self.get(&actor);
+        if counter <= current {
+            return Err(Dot::new(actor, current));
+        }
+        self.dots.insert(actor, counter);
+        Ok(())
+    }
+
+    /// Merge `other` into this vclock, returning the actors whose counters increased.
+    pub fn merge_tracking(&mut self, other: &VClock<A>) -> Vec<A> {
+        let mut advanced = Vec::new();
+        for (actor, counter) in other.dots.iter() {
+            if self.get(actor) < *counter {
+                self.dots.insert(actor.clone(), *counter);
+                advanced.push(actor.clone());
+            }
+        }
+        advanced
+    }
+
+    /// Merge `other` into this vclock unless this vclock already covers it, returning
+    /// whether `other` brought anything new.
+    ///
+    /// Redelivered clocks are usually dominated, this checks for it in a single ordered walk of
+    /// both clocks instead of a lookup per actor of `other`, and leaves this vclock untouched.
+    pub fn merge_if_newer(&mut self, other: &VClock<A>) -> bool {
+        if self.covers(other) {
+            return false;
+        }
+        for (actor, counter) in other.dots.iter() {
+            self.merge_dot(Dot::new(actor.clone(), *counter));
+        }
+        true
+    }
+
+    /// True if every counter of `other` is at most the counter of this vclock for that actor.
+    fn covers(&self, other: &VClock<A>) -> bool {
+        let mut mine = self.dots.iter().peekable();
+        other.dots.iter().all(|(actor, counter)| {
+            while mine.peek().map(|(a, _)| *a < actor).unwrap_or(false) {
+                mine.next();
+            }
+            match mine.peek() {
+                Some((a, c)) if *a == actor => *c >= counter,
+                _ => *counter == 0,
+            }
+        })
+    }
+
+    /// Merge a batch of dots into this vclock, keeping the largest counter seen for each actor.
+    ///
+    /// This is [`VClock::merge_from`] for any iterator of dots, without collecting them first.
+    pub fn merge_dots<I: IntoIterator<Item = Dot<A>>>(&mut self, dots: I) {
+        for dot in dots {
+            self.merge_dot(dot);
+        }
+    }
+
     /// Generate Op to increment an actor's counter.
     ///
     /// # Examples
@@ -169,6 +449,36 @@ impl<A: Actor> VClock<A> {
         self.dot(actor).inc()
     }
 
+    /// Generate Op to increment an actor's counter, failing with [`Error::CounterOverflow`]
+    /// instead of saturating when the counter is already at `u64::MAX`.
+    ///
+    /// [`VClock::inc`] saturates, its op is then a dot the clock has already seen and applying
+    /// it is a no-op.
+    pub fn try_inc(&self, actor: A) -> Result<Dot<A>, Error> {
+        let counter = self.get(&actor);
+        let next = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+        Ok(Dot::new(actor, next))
+    }
+
+    /// Increment an actor's counter in place, returning the dot of the new event.
+    ///
+    /// This is `inc` immediately followed by applying its op.
+    pub fn apply_inc(&mut self, actor: A) -> Dot<A> {
+        let dot = self.inc(actor);
+        self.apply(dot.clone());
+        dot
+    }
+
+    /// Increment the counter of every actor tracked by this clock by one.
+    ///
+    /// Actors that are not in the clock are left untouched. Counters saturate at `u64::MAX`,
+    /// like [`Dot::inc`].
+    pub fn advance_all(&mut self) {
+        for counter in self.dots.values_mut() {
+            *counter = counter.saturating_add(1);
+        }
+    }
+
     /// Return the associated counter for this actor.
     /// All actors not in the vclock have an implied count of 0
     pub fn get(&self, actor: &A) -> u64 {
@@ -181,6 +491,78 @@ impl<A: Actor> VClock<A> {
         Dot::new(actor, counter)
     }
 
+    /// True if the event marked by `dot` is in the causal past of this clock.
+    pub fn has_seen(&self, dot: &Dot<A>) -> bool {
+        self.get(&dot.actor) >= dot.counter
+    }
+
+    /// True if `dot` is exactly the next event this clock expects from its actor.
+    ///
+    /// Delivering only dots for which this holds guarantees there are no gaps in the
+    /// events seen from each actor.
+    pub fn is_next(&self, dot: &Dot<A>) -> bool {
+        dot.counter.checked_sub(1) == Some(self.get(&dot.actor))
+    }
+
+    /// Panics in debug builds if this clock and `other` are concurrent, does nothing in release
+    /// builds.
+    ///
+    /// Meant to catch stray writers where a single writer is expected: with one writer every
+    /// pair of clocks it produces is ordered.
+    pub fn debug_assert_comparable(&self, other: &VClock<A>)
+    where
+        A: fmt::Debug,
+    {
+        debug_assert!(
+            self.partial_cmp(other).is_some(),
+            "concurrent clocks {:?} and {:?}",
+            self,
+            other
+        );
+    }
+
+    /// True if at least `quorum` of `clocks` have seen the event marked by `dot`.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{Dot, VClock};
+    /// let clocks = vec![VClock::from_slice(&[("A", 2)]), VClock::from_slice(&[("A", 1)])];
+    /// assert!(VClock::seen_by_quorum(&Dot::new("A", 1), &clocks, 2));
+    /// assert!(!VClock::seen_by_quorum(&Dot::new("A", 2), &clocks, 2));
+    /// ```
+    pub fn seen_by_quorum(dot: &Dot<A>, clocks: &[VClock<A>], quorum: usize) -> bool {
+        clocks.iter().filter(|clock| clock.has_seen(dot)).count() >= quorum
+    }
+
+    /// True if this clock has seen every event of `other` and more.
+    pub fn dominates(&self, other: &VClock<A>) -> bool {
+        self.relation(other) == Relation::Dominates
+    }
+
+    /// The causal relation of this clock to `other`, `partial_cmp` with the concurrent case
+    /// named.
+    pub fn relation(&self, other: &VClock<A>) -> Relation {
+        match self.partial_cmp(other) {
+            Some(Ordering::Equal) => Relation::Equal,
+            Some(Ordering::Greater) => Relation::Dominates,
+            Some(Ordering::Less) => Relation::Dominated,
+            None => Relation::Concurrent,
+        }
+    }
+
+    /// True if this clock extends `base` only by advancing actors that `base` already tracks.
+    ///
+    /// This identifies a linear history, e.g. with a single writer: a clock that introduces
+    /// actors unknown to `base` is treated as a branch even if it dominates `base`.
+    pub fn is_linear_extension_of(&self, base: &VClock<A>) -> bool {
+        self >= base && self.dots.keys().all(|actor| base.dots.contains_key(actor))
+    }
+
+    /// True if every dot in the given range has been seen by this clock.
+    pub fn contains_range(&self, range: &DotRange<A>) -> bool {
+        self.get(&range.actor) >= range.end
+    }
+
     /// True if two vector clocks have diverged.
     ///
     /// # Examples
@@ -195,9 +577,57 @@ impl<A: Actor> VClock<A> {
         self.partial_cmp(other).is_none()
     }
 
-    /// Returns `true` if this vector clock contains nothing.
+    /// Returns the number of actors tracked by this vector clock.
+    pub fn len(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// Returns `true` if this vector clock contains nothing, i.e. `len() == 0`.
     pub fn is_empty(&self) -> bool {
-        self.dots.is_empty()
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the actors tracked by this vector clock, in ascending order.
+    pub fn actors(&self) -> impl Iterator<Item = &A> + '_ {
+        self.dots.keys()
+    }
+
+    /// Returns `true` if this vector clock tracks `actor`.
+    pub fn contains_actor(&self, actor: &A) -> bool {
+        self.dots.contains_key(actor)
+    }
+
+    /// Stop tracking `actor`, returning its counter if it was tracked.
+    pub fn remove_actor(&mut self, actor: &A) -> Option<u64> {
+        self.dots.remove(actor)
+    }
+
+    /// Forget every actor, leaving an empty vector clock.
+    ///
+    /// This is the same as assigning `VClock::new()`. Note that the underlying `BTreeMap` keeps
+    /// no spare capacity, so there is no allocation to reuse either way.
+    pub fn clear(&mut self) {
+        self.dots.clear();
+    }
+
+    /// Keep only the actors for which `pred` returns true given the actor and its counter.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::VClock;
+    /// let mut clock = VClock::from_slice(&[("A", 7), ("B", 2), ("C", 5)]);
+    /// clock.retain(|_, counter| counter >= 5);
+    /// assert_eq!(clock, VClock::from_slice(&[("A", 7), ("C", 5)]));
+    /// ```
+    pub fn retain<F: FnMut(&A, u64) -> bool>(&mut self, mut pred: F) {
+        self.dots.retain(|actor, counter| pred(actor, *counter));
+    }
+
+    /// Forget the actors of this clock that `other` has caught up with, see `Causal::forget`,
+    /// and return `true` if nothing is left, i.e. if `other` fully supersedes this clock.
+    pub fn forget_is_empty(&mut self, other: &Self) -> bool {
+        self.forget(other);
+        self.is_empty()
     }
 
     /// Returns the common elements (same actor and counter)
@@ -231,7 +661,7 @@ impl<A: Actor> VClock<A> {
     /// assert_eq!(c.get(&43), 0);
     /// ```
     pub fn glb(&mut self, other: &Self) {
-        self.dots = mem::replace(&mut self.dots, BTreeMap::new())
+        self.dots = std::mem::take(&mut self.dots)
             .into_iter()
             .filter_map(|(actor, count)| {
                 // Since an actor missing from the dots map has an implied
@@ -245,6 +675,103 @@ impl<A: Actor> VClock<A> {
             .collect();
     }
 
+    /// Returns the greatest-lower-bound of all the given clocks, the empty clock if there are
+    /// none.
+    ///
+    /// Stops consuming `clocks` as soon as the running bound is empty since no other clock
+    /// can grow it back.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::VClock;
+    /// let clocks = vec![
+    ///     VClock::from_slice(&[("A", 3), ("B", 2)]),
+    ///     VClock::from_slice(&[("A", 1), ("B", 4)]),
+    ///     VClock::from_slice(&[("A", 2), ("B", 1), ("C", 1)]),
+    /// ];
+    /// assert_eq!(VClock::glb_all(clocks), VClock::from_slice(&[("A", 1), ("B", 1)]));
+    /// ```
+    pub fn glb_all<I: IntoIterator<Item = VClock<A>>>(clocks: I) -> VClock<A> {
+        let mut clocks = clocks.into_iter();
+        let mut glb = match clocks.next() {
+            Some(clock) => clock,
+            None => return VClock::new(),
+        };
+        for clock in clocks {
+            if glb.is_empty() {
+                break;
+            }
+            glb.glb(&clock);
+        }
+        glb
+    }
+
+    /// Returns the least-upper-bound of two VClocks without modifying either of them, as an
+    /// example see the following code.
+    /// ``` rust
+    /// use crdts::{VClock, Dot, CmRDT};
+    /// let mut a = VClock::new();
+    /// a.apply(Dot::new(23, 6));
+    /// a.apply(Dot::new(89, 14));
+    ///
+    /// assert_eq!(VClock::lub(&a, &a), a); // `lub { a, a } = a`
+    ///
+    /// let mut b = VClock::new();
+    /// b.apply(Dot::new(43, 1));
+    /// b.apply(Dot::new(89, 2));
+    /// let lub = VClock::lub(&a, &b);
+    /// assert_eq!(lub.get(&23), 6);
+    /// assert_eq!(lub.get(&43), 1); // missing actors count as 0
+    /// assert_eq!(lub.get(&89), 14);
+    /// ```
+    pub fn lub(a: &VClock<A>, b: &VClock<A>) -> VClock<A> {
+        let mut lub = a.clone();
+        lub += b;
+        lub
+    }
+
+    /// Returns a clock containing only the entries of the given actors.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{VClock, Dot, CmRDT};
+    /// let mut c = VClock::new();
+    /// c.apply(Dot::new("A", 1));
+    /// c.apply(Dot::new("B", 2));
+    ///
+    /// let projected = c.project(&vec!["A", "C"].into_iter().collect());
+    /// assert_eq!(projected, VClock::from(Dot::new("A", 1)));
+    /// ```
+    pub fn project(&self, actors: &BTreeSet<A>) -> Self {
+        let dots = self
+            .dots
+            .iter()
+            .filter(|(actor, _)| actors.contains(actor))
+            .map(|(actor, counter)| (actor.clone(), *counter))
+            .collect();
+        Self { dots }
+    }
+
+    /// Compares two clocks considering only the entries of the given actors.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{VClock, Dot, CmRDT};
+    /// use std::cmp::Ordering;
+    /// let mut a = VClock::new();
+    /// a.apply(Dot::new("A", 2));
+    /// let mut b = VClock::new();
+    /// b.apply(Dot::new("A", 1));
+    /// b.apply(Dot::new("B", 1));
+    ///
+    /// assert!(a.concurrent(&b));
+    /// let actors = vec!["A"].into_iter().collect();
+    /// assert_eq!(a.partial_cmp_projected(&b, &actors), Some(Ordering::Greater));
+    /// ```
+    pub fn partial_cmp_projected(&self, other: &Self, actors: &BTreeSet<A>) -> Option<Ordering> {
+        self.project(actors).partial_cmp(&other.project(actors))
+    }
+
     /// Returns an iterator over the dots in this vclock
     pub fn iter(&self) -> impl Iterator<Item = Dot<&A>> {
         self.dots.iter().map(|(a, c)| Dot {
@@ -252,6 +779,712 @@ impl<A: Actor> VClock<A> {
             counter: *c,
         })
     }
+
+    /// A snapshot of the counter of every actor in this vclock.
+    pub fn counter_histogram(&self) -> BTreeMap<A, u64> {
+        self.dots.clone()
+    }
+
+    /// The difference between the largest and the smallest counter in this vclock.
+    ///
+    /// A large skew points at an actor lagging behind the others. Returns 0 for an empty clock.
+    pub fn skew(&self) -> u64 {
+        let min = self.dots.values().min().cloned().unwrap_or(0);
+        self.max_counter() - min
+    }
+
+    /// The largest counter reached by any actor, 0 for an empty clock.
+    pub fn max_counter(&self) -> u64 {
+        self.dots.values().max().cloned().unwrap_or(0)
+    }
+
+    /// The number of events this clock has seen: the sum of its counters, saturating at
+    /// `u64::MAX`.
+    pub fn total_events(&self) -> u64 {
+        self.dots
+            .values()
+            .fold(0u64, |total, counter| total.saturating_add(*counter))
+    }
+
+    /// Returns the dots of the actors that fall within `range`, in actor order.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{Dot, VClock};
+    /// let clock: VClock<u8> = vec![Dot::new(1, 1), Dot::new(12, 3)].into_iter().collect();
+    /// assert_eq!(clock.range(10..20).collect::<Vec<_>>(), vec![Dot::new(12, 3)]);
+    /// ```
+    pub fn range<R: RangeBounds<A>>(&self, range: R) -> impl Iterator<Item = Dot<A>> + '_ {
+        self.dots.range(range).map(|(a, c)| Dot::new(a.clone(), *c))
+    }
+
+    /// Returns the dots in this vclock sorted by actor.
+    ///
+    /// The order is guaranteed regardless of how the clock stores its dots, making this suited
+    /// for building content hashes or stable logs.
+    pub fn to_sorted_vec(&self) -> Vec<Dot<A>> {
+        self.dots_iter().collect()
+    }
+
+    /// Returns an iterator over owned copies of the dots in this vclock, in actor order.
+    ///
+    /// Unlike [`VClock::iter`] the dots own their actor so they can be handed to the many
+    /// `Dot` based APIs without rebuilding them.
+    pub fn dots_iter(&self) -> impl Iterator<Item = Dot<A>> + '_ {
+        self.dots.iter().map(|(a, c)| Dot::new(a.clone(), *c))
+    }
+
+    /// Returns an iterator over every event this clock has seen, that is every
+    /// dot from `1` up to the counter of each actor.
+    ///
+    /// Note that this yields as many dots as the sum of all counters, which can be
+    /// very large for long lived clocks.
+    pub fn events(&self) -> impl Iterator<Item = Dot<A>> + '_ {
+        self.dots
+            .iter()
+            .flat_map(|(actor, counter)| (1..=*counter).map(move |c| Dot::new(actor.clone(), c)))
+    }
+
+    /// Renders the clock as a JSON object keyed by human readable actor names, meant for
+    /// debugging tools rather than as a wire format.
+    ///
+    /// Actors missing from `labels` are named by their `Debug` representation.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use crdts::VClock;
+    /// let clock = VClock::from_slice(&[(1, 3), (2, 1)]);
+    /// let labels: BTreeMap<_, _> = vec![(1, "alice".to_string())].into_iter().collect();
+    /// assert_eq!(clock.to_labeled_json(&labels), r#"{ "alice": 3, "2": 1 }"#);
+    /// ```
+    pub fn to_labeled_json(&self, labels: &BTreeMap<A, String>) -> String
+    where
+        A: fmt::Debug,
+    {
+        if self.dots.is_empty() {
+            return "{}".to_string();
+        }
+        let fields: Vec<String> = self
+            .dots
+            .iter()
+            .map(|(actor, counter)| {
+                let label = match labels.get(actor) {
+                    Some(label) => label.clone(),
+                    None => format!("{:?}", actor),
+                };
+                format!("{}: {}", json_string(&label), counter)
+            })
+            .collect();
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+/// Quotes `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A set of clocks stored as deltas against the history they all share.
+///
+/// The shared history is the greatest-lower-bound of all clocks in the set, each clock is then
+/// stored as only the entries where it is ahead of that base. This saves space when persisting
+/// many clocks that have mostly seen the same events.
+///
+/// # Examples
+/// ```
+/// use crdts::{VClock, Dot};
+/// use crdts::vclock::ClockSet;
+/// let a: VClock<_> = vec![Dot::new("A", 10), Dot::new("B", 4)].into_iter().collect();
+/// let b: VClock<_> = vec![Dot::new("A", 10), Dot::new("B", 5)].into_iter().collect();
+///
+/// let set = ClockSet::new(vec![a.clone(), b.clone()]);
+/// assert_eq!(set.base(), &vec![Dot::new("A", 10), Dot::new("B", 4)].into_iter().collect());
+/// assert_eq!(set.clocks().collect::<Vec<_>>(), vec![a, b]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockSet<A: Actor> {
+    base: VClock<A>,
+    deltas: Vec<VClock<A>>,
+}
+
+impl<A: Actor> ClockSet<A> {
+    /// Build a set from the given clocks, the order of the clocks is preserved.
+    pub fn new<I: IntoIterator<Item = VClock<A>>>(clocks: I) -> Self {
+        let clocks: Vec<_> = clocks.into_iter().collect();
+        let mut base = clocks.first().cloned().unwrap_or_default();
+        for clock in clocks.iter().skip(1) {
+            base.glb(clock);
+        }
+
+        let deltas = clocks
+            .into_iter()
+            .map(|clock| clock.clone_without(&base))
+            .collect();
+
+        Self { base, deltas }
+    }
+
+    /// The history shared by every clock in the set
+    pub fn base(&self) -> &VClock<A> {
+        &self.base
+    }
+
+    /// The entries of each clock that are ahead of the base
+    pub fn deltas(&self) -> &[VClock<A>] {
+        &self.deltas
+    }
+
+    /// The number of clocks in the set
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    /// Returns `true` if the set holds no clocks
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Reconstruct the clock at the given position
+    pub fn get(&self, ix: usize) -> Option<VClock<A>> {
+        self.deltas.get(ix).map(|delta| self.rebase(delta))
+    }
+
+    /// Reconstruct every clock in the set
+    pub fn clocks(&self) -> impl Iterator<Item = VClock<A>> + '_ {
+        self.deltas.iter().map(move |delta| self.rebase(delta))
+    }
+
+    fn rebase(&self, delta: &VClock<A>) -> VClock<A> {
+        let mut clock = self.base.clone();
+        clock.merge(delta.clone());
+        clock
+    }
+}
+
+/// A byte encoding of clocks with integer actors, meant for persisting large numbers of clocks.
+///
+/// Actors are delta encoded in order and both deltas and counters are written as LEB128
+/// varints, so clocks with small counters take a few bytes per actor instead of 16. This is
+/// independent from the serde representation, which is unaffected.
+impl<A: Actor + Copy + Into<u64> + TryFrom<u64>> VClock<A> {
+    /// Encode this clock into its compressed byte form.
+    ///
+    /// # Examples
+    /// ```
+    /// use crdts::{Dot, VClock};
+    /// let clock: VClock<u32> = vec![Dot::new(3, 1), Dot::new(7, 300)].into_iter().collect();
+    /// let bytes = clock.compressed_bytes();
+    /// assert_eq!(bytes, vec![2, 3, 1, 4, 0xac, 0x02]);
+    /// assert_eq!(VClock::from_compressed_bytes(&bytes), Some(clock));
+    /// ```
+    pub fn compressed_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, self.dots.len() as u64);
+        let mut prev = 0;
+        for (actor, counter) in self.dots.iter() {
+            let actor: u64 = (*actor).into();
+            write_varint(&mut bytes, actor - prev);
+            write_varint(&mut bytes, *counter);
+            prev = actor;
+        }
+        bytes
+    }
+
+    /// Decode a clock written by [`VClock::compressed_bytes`].
+    ///
+    /// Returns `None` if the bytes are not a valid encoding for this actor type.
+    pub fn from_compressed_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let len = read_varint(&mut bytes)?;
+        let mut clock = Self::new();
+        let mut prev: u64 = 0;
+        for i in 0..len {
+            let delta = read_varint(&mut bytes)?;
+            if i > 0 && delta == 0 {
+                // actors are strictly increasing
+                return None;
+            }
+            let actor = prev.checked_add(delta)?;
+            let counter = read_varint(&mut bytes)?;
+            clock.dots.insert(A::try_from(actor).ok()?, counter);
+            prev = actor;
+        }
+
+        if bytes.is_empty() {
+            Some(clock)
+        } else {
+            None
+        }
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        bytes.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut n: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        if shift == 63 && byte & 0x7e != 0 {
+            // only the lowest bit of the 10th byte fits in a u64
+            return None;
+        }
+        n |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            // a zero last byte pads the encoding, write_varint never produces one
+            return if shift > 0 && *byte == 0 {
+                None
+            } else {
+                Some(n)
+            };
+        }
+    }
+    None
+}
+
+/// A dense vector clock for actors that are densely packed small integers.
+///
+/// Counters are stored in a `Vec` indexed by actor, so merging and taking the glb of two clocks
+/// are element-wise `max` and `min` over contiguous arrays, which the compiler can turn into
+/// branchless SIMD loops. This is much faster than walking a `VClock` for clocks with thousands
+/// of actors, but takes memory proportional to the largest actor: only use it when actors are
+/// packed close to 0.
+///
+/// # Examples
+/// ```
+/// use crdts::{CvRDT, Dot, VClock};
+/// use crdts::vclock::DenseClock;
+/// let a: VClock<u16> = vec![Dot::new(0, 3), Dot::new(2, 1)].into_iter().collect();
+/// let b: VClock<u16> = vec![Dot::new(1, 2), Dot::new(2, 4)].into_iter().collect();
+///
+/// let mut dense = DenseClock::from(&a);
+/// dense.merge(&DenseClock::from(&b));
+/// assert_eq!(dense.get(2), 4);
+///
+/// let mut merged = a.clone();
+/// merged.merge(b);
+/// assert_eq!(VClock::from(dense), merged);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DenseClock<A> {
+    counters: Vec<u64>,
+    #[serde(skip)]
+    actor: PhantomData<A>,
+}
+
+impl<A> Default for DenseClock<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> DenseClock<A> {
+    /// Returns a new, empty `DenseClock`.
+    pub fn new() -> Self {
+        Self {
+            counters: Vec::new(),
+            actor: PhantomData,
+        }
+    }
+
+    /// Keep the largest counter of each actor between this clock and `other`.
+    pub fn merge(&mut self, other: &Self) {
+        if self.counters.len() < other.counters.len() {
+            self.counters.resize(other.counters.len(), 0);
+        }
+        for (a, b) in self.counters.iter_mut().zip(other.counters.iter()) {
+            *a = cmp::max(*a, *b);
+        }
+    }
+
+    /// Keep the smallest counter of each actor between this clock and `other`.
+    pub fn glb(&mut self, other: &Self) {
+        self.counters.truncate(other.counters.len());
+        for (a, b) in self.counters.iter_mut().zip(other.counters.iter()) {
+            *a = cmp::min(*a, *b);
+        }
+        while self.counters.last() == Some(&0) {
+            self.counters.pop();
+        }
+    }
+}
+
+impl<A: Into<u64>> DenseClock<A> {
+    /// Return the counter for this actor, 0 if the clock hasn't seen it.
+    pub fn get(&self, actor: A) -> u64 {
+        let ix = actor.into() as usize;
+        self.counters.get(ix).cloned().unwrap_or(0)
+    }
+}
+
+impl<A: Actor + Copy + Into<u64>> From<&VClock<A>> for DenseClock<A> {
+    fn from(clock: &VClock<A>) -> Self {
+        let mut dense = Self::new();
+        if let Some(max) = clock.dots.keys().last() {
+            dense.counters.resize((*max).into() as usize + 1, 0);
+        }
+        for (actor, counter) in clock.dots.iter() {
+            dense.counters[(*actor).into() as usize] = *counter;
+        }
+        dense
+    }
+}
+
+impl<A: Actor + TryFrom<u64>> From<DenseClock<A>> for VClock<A> {
+    fn from(dense: DenseClock<A>) -> Self {
+        let mut clock = VClock::new();
+        for (ix, counter) in dense.counters.into_iter().enumerate() {
+            if counter > 0 {
+                // every non-zero counter was set from an actor of type `A`
+                let actor = A::try_from(ix as u64)
+                    .unwrap_or_else(|_| panic!("actor {} is not a valid actor", ix));
+                clock.dots.insert(actor, counter);
+            }
+        }
+        clock
+    }
+}
+
+/// A vector clock that also keeps the dots received past the contiguous history of each actor.
+///
+/// A `VClock` only stores the latest counter of each actor, so it can't tell whether every
+/// event before it was received. This clock keeps the contiguous history in a `VClock` and the
+/// dots received out of order beside it, which lets it report the missing events of an actor.
+///
+/// # Examples
+/// ```
+/// use crdts::{CmRDT, Dot};
+/// use crdts::vclock::DottedVClock;
+/// let mut clock = DottedVClock::new();
+/// clock.apply(Dot::new("A", 1));
+/// clock.apply(Dot::new("A", 4));
+/// assert_eq!(clock.gaps(&"A"), vec![2, 3]);
+///
+/// clock.apply(Dot::new("A", 2));
+/// clock.apply(Dot::new("A", 3));
+/// assert!(clock.gaps(&"A").is_empty());
+/// assert_eq!(clock.contiguous().get(&"A"), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DottedVClock<A: Actor> {
+    /// The events of each actor received without gaps
+    contiguous: VClock<A>,
+    /// The counters received past the contiguous history of each actor
+    received: BTreeMap<A, BTreeSet<u64>>,
+}
+
+impl<A: Actor> Default for DottedVClock<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Actor> DottedVClock<A> {
+    /// Returns a new, empty `DottedVClock`.
+    pub fn new() -> Self {
+        Self {
+            contiguous: VClock::new(),
+            received: BTreeMap::new(),
+        }
+    }
+
+    /// The events received from each actor with no gaps before them.
+    pub fn contiguous(&self) -> &VClock<A> {
+        &self.contiguous
+    }
+
+    /// The latest event received from each actor, gaps included.
+    pub fn frontier(&self) -> VClock<A> {
+        let mut frontier = self.contiguous.clone();
+        for (actor, counters) in self.received.iter() {
+            if let Some(counter) = counters.iter().next_back() {
+                frontier.merge_dot(Dot::new(actor.clone(), *counter));
+            }
+        }
+        frontier
+    }
+
+    /// True if the event marked by `dot` was received.
+    pub fn has_seen(&self, dot: &Dot<A>) -> bool {
+        self.contiguous.has_seen(dot)
+            || self
+                .received
+                .get(&dot.actor)
+                .map(|counters| counters.contains(&dot.counter))
+                .unwrap_or(false)
+    }
+
+    /// The counters of `actor` that were not received although a later one was.
+    pub fn gaps(&self, actor: &A) -> Vec<u64> {
+        let counters = match self.received.get(actor) {
+            Some(counters) => counters,
+            None => return Vec::new(),
+        };
+        let latest = counters.iter().next_back().cloned().unwrap_or(0);
+        (self.contiguous.get(actor) + 1..latest)
+            .filter(|counter| !counters.contains(counter))
+            .collect()
+    }
+}
+
+impl<A: Actor> CmRDT for DottedVClock<A> {
+    type Op = Dot<A>;
+
+    /// Records the event marked by `dot`, extending the contiguous history of its actor when
+    /// it fills the first gap.
+    fn apply(&mut self, dot: Self::Op) {
+        if self.contiguous.has_seen(&dot) {
+            return;
+        }
+        let counters = self.received.entry(dot.actor.clone()).or_default();
+        counters.insert(dot.counter);
+
+        let mut next = self.contiguous.get(&dot.actor) + 1;
+        while counters.remove(&next) {
+            next += 1;
+        }
+        if counters.is_empty() {
+            self.received.remove(&dot.actor);
+        }
+        self.contiguous.merge_dot(Dot::new(dot.actor, next - 1));
+    }
+}
+
+/// A vector clock that remembers the final counters of actors that permanently left.
+///
+/// Retiring an actor drops it from the `VClock`, so it no longer takes part in comparisons,
+/// but its final counter is kept beside the clock so that stale messages from it are still
+/// recognized as seen.
+///
+/// # Examples
+/// ```
+/// use crdts::{Dot, VClock};
+/// use crdts::vclock::RetiringClock;
+/// let mut clock = RetiringClock::from(VClock::from_slice(&[("A", 4), ("B", 2)]));
+/// clock.retire(&"A");
+/// assert_eq!(clock.clock(), &VClock::from(Dot::new("B", 2)));
+/// assert!(clock.has_seen(&Dot::new("A", 3)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RetiringClock<A: Actor> {
+    /// The events of the actors still tracked
+    clock: VClock<A>,
+    /// The final counter of every retired actor
+    retired: BTreeMap<A, u64>,
+}
+
+impl<A: Actor> Default for RetiringClock<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Actor> From<VClock<A>> for RetiringClock<A> {
+    fn from(clock: VClock<A>) -> Self {
+        Self {
+            clock,
+            retired: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A: Actor> RetiringClock<A> {
+    /// Returns a new, empty `RetiringClock`.
+    pub fn new() -> Self {
+        Self::from(VClock::new())
+    }
+
+    /// The clock of the actors still tracked.
+    pub fn clock(&self) -> &VClock<A> {
+        &self.clock
+    }
+
+    /// The final counter of every retired actor.
+    pub fn retired(&self) -> &BTreeMap<A, u64> {
+        &self.retired
+    }
+
+    /// Stop tracking an actor that has permanently left, recording its final counter.
+    ///
+    /// Retiring an actor the clock doesn't track does nothing.
+    pub fn retire(&mut self, actor: &A) {
+        if let Some(counter) = self.clock.remove_actor(actor) {
+            let retired = self.retired.entry(actor.clone()).or_insert(0);
+            *retired = cmp::max(*retired, counter);
+        }
+    }
+
+    /// The counter of `actor`, counting the events it had when it was retired.
+    fn seen_counter(&self, actor: &A) -> u64 {
+        let retired = self.retired.get(actor).cloned().unwrap_or(0);
+        cmp::max(self.clock.get(actor), retired)
+    }
+
+    /// True if the event marked by `dot` was seen, the events of retired actors included.
+    pub fn has_seen(&self, dot: &Dot<A>) -> bool {
+        self.seen_counter(&dot.actor) >= dot.counter
+    }
+
+    /// True if every dot in the given range was seen, the events of retired actors included.
+    pub fn contains_range(&self, range: &DotRange<A>) -> bool {
+        self.seen_counter(&range.actor) >= range.end
+    }
+}
+
+impl<A: Actor> CmRDT for RetiringClock<A> {
+    type Op = Dot<A>;
+
+    /// Records the event marked by `dot`, stale events of retired actors are ignored.
+    fn apply(&mut self, dot: Self::Op) {
+        if !self.has_seen(&dot) {
+            self.clock.apply(dot);
+        }
+    }
+}
+
+impl<A: Actor> AddAssign<&RetiringClock<A>> for RetiringClock<A> {
+    /// Merges `other` into this clock, `a += &b` is `a.merge(b.clone())` without the clone.
+    ///
+    /// Actors retired by either side stay retired, unless the other side saw events of theirs
+    /// past the retirement.
+    fn add_assign(&mut self, other: &RetiringClock<A>) {
+        for (actor, counter) in other.retired.iter() {
+            let retired = self.retired.entry(actor.clone()).or_insert(0);
+            *retired = cmp::max(*retired, *counter);
+        }
+        self.clock += &other.clock;
+
+        let retired = &self.retired;
+        self.clock
+            .retain(|actor, counter| !matches!(retired.get(actor), Some(r) if counter <= *r));
+    }
+}
+
+impl<A: Actor> CvRDT for RetiringClock<A> {
+    fn merge(&mut self, other: Self) {
+        *self += &other;
+    }
+}
+
+/// A compact serde representation of a `VClock` as a sequence of dots.
+///
+/// The default representation is a map from actor to counter, which formats like JSON
+/// can't encode when actors are not strings or integers. Use this module to store the clock
+/// as a list of dots instead:
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use crdts::VClock;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Versioned {
+///     #[serde(with = "crdts::vclock::compact")]
+///     clock: VClock<(u64, u64)>,
+/// }
+/// ```
+pub mod compact {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Actor, Dot, VClock};
+
+    /// Serialize a clock as a sequence of dots
+    pub fn serialize<A, S>(clock: &VClock<A>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        A: Actor + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(clock.iter())
+    }
+
+    /// Deserialize a clock from a sequence of dots
+    pub fn deserialize<'de, A, D>(deserializer: D) -> Result<VClock<A>, D::Error>
+    where
+        A: Actor + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let dots = Vec::<Dot<A>>::deserialize(deserializer)?;
+        Ok(dots.into_iter().collect())
+    }
+}
+
+/// A memoizing comparator for hot loops that compare the same clocks over and over.
+///
+/// This is opt-in: results are cached by the address of the compared clocks, which is
+/// only sound as long as those clocks are not mutated. The comparer borrows every clock
+/// it compares, so it must be dropped before any of them can be mutated again.
+///
+/// # Examples
+/// ```
+/// use crdts::{VClock, Dot, CmRDT};
+/// use crdts::vclock::ClockComparer;
+/// let a = VClock::from(Dot::new("A", 2));
+/// let b = VClock::from(Dot::new("A", 1));
+///
+/// let mut comparer = ClockComparer::new();
+/// for _ in 0..10 {
+///     assert!(comparer.partial_cmp(&a, &b) == a.partial_cmp(&b));
+/// }
+/// assert_eq!(comparer.comparisons(), 1);
+/// ```
+pub struct ClockComparer<'a, A: Actor> {
+    cache: HashMap<(usize, usize), Option<Ordering>>,
+    comparisons: usize,
+    clocks: PhantomData<&'a VClock<A>>,
+}
+
+impl<'a, A: Actor> Default for ClockComparer<'a, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, A: Actor> ClockComparer<'a, A> {
+    /// Returns a new comparer with an empty cache.
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            comparisons: 0,
+            clocks: PhantomData,
+        }
+    }
+
+    /// Compares two clocks, reusing a previous result for the same pair if we have one.
+    pub fn partial_cmp(&mut self, a: &'a VClock<A>, b: &'a VClock<A>) -> Option<Ordering> {
+        let (a_key, b_key) = (a as *const _ as usize, b as *const _ as usize);
+        if let Some(ord) = self.cache.get(&(a_key, b_key)) {
+            return *ord;
+        }
+        if let Some(ord) = self.cache.get(&(b_key, a_key)) {
+            return ord.map(Ordering::reverse);
+        }
+
+        self.comparisons += 1;
+        let ord = a.partial_cmp(b);
+        self.cache.insert((a_key, b_key), ord);
+        ord
+    }
+
+    /// The number of comparisons that were not answered by the cache.
+    pub fn comparisons(&self) -> usize {
+        self.comparisons
+    }
 }
 
 /// Generated from calls to VClock::into_iter()
@@ -273,7 +1506,8 @@ impl<A: Actor> std::iter::IntoIterator for VClock<A> {
     type Item = Dot<A>;
     type IntoIter = IntoIter<A>;
 
-    /// Consumes the vclock and returns an iterator over dots in the clock
+    /// Consumes the vclock and returns an iterator over dots in the clock, in ascending actor
+    /// order
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
             btree_iter: self.dots.into_iter(),
@@ -284,9 +1518,28 @@ impl<A: Actor> std::iter::IntoIterator for VClock<A> {
 impl<A: Actor> std::iter::FromIterator<Dot<A>> for VClock<A> {
     fn from_iter<I: IntoIterator<Item = Dot<A>>>(iter: I) -> Self {
         let mut clock = VClock::new();
+        clock.extend(iter);
+        clock
+    }
+}
 
+impl<A: Actor> Extend<Dot<A>> for VClock<A> {
+    /// Applies every dot, keeping the largest counter of each actor whatever the order of the
+    /// dots.
+    fn extend<I: IntoIterator<Item = Dot<A>>>(&mut self, iter: I) {
         for dot in iter {
-            clock.apply(dot);
+            self.apply(dot);
+        }
+    }
+}
+
+impl<A: Actor> std::iter::FromIterator<VClock<A>> for VClock<A> {
+    /// Collects clocks into their least upper bound.
+    fn from_iter<I: IntoIterator<Item = VClock<A>>>(iter: I) -> Self {
+        let mut clock = VClock::new();
+
+        for other in iter {
+            clock.merge(other);
         }
 
         clock
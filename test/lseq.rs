@@ -1,7 +1,13 @@
-use crdts::lseq::{LSeq, Op};
-use crdts::CmRDT;
+use crdts::lseq::history::History;
+use crdts::lseq::replica::Replica;
+use crdts::lseq::{ChangeEvent, LSeq, Op};
+use crdts::{CmRDT, Dot, Error, VClock};
 use rand::distributions::Alphanumeric;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 type SiteId = u32;
 #[derive(Debug, Clone)]
@@ -23,7 +29,7 @@ impl Arbitrary for OperationList {
         let mut site1 = LSeq::new(g.gen());
         let ops = (0..size)
             .filter_map(|_| {
-                if g.gen() || site1.len() == 0 {
+                if g.gen() || site1.is_empty() {
                     site1.delete_index(g.gen_range(0, site1.len() + 1))
                 } else {
                     site1.delete_index(g.gen_range(0, site1.len()))
@@ -105,6 +111,129 @@ fn test_get() {
     assert_eq!(site1.get(1), Some(&'b'));
 }
 
+#[test]
+fn test_apply_with_reports_changes() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let mut events = Vec::new();
+    site2.apply_with(site1.append('a'), |e| events.push(e));
+    site2.apply_with(site1.append('c'), |e| events.push(e));
+    site2.apply_with(site1.insert_index(1, 'b'), |e| events.push(e));
+    site2.apply_with(site1.delete_index(0).unwrap(), |e| events.push(e));
+
+    assert_eq!(
+        events,
+        vec![
+            ChangeEvent::Inserted {
+                index: 0,
+                value: 'a'
+            },
+            ChangeEvent::Inserted {
+                index: 1,
+                value: 'c'
+            },
+            ChangeEvent::Inserted {
+                index: 1,
+                value: 'b'
+            },
+            ChangeEvent::Removed {
+                index: 0,
+                value: 'a'
+            },
+        ]
+    );
+    assert_eq!(site2.iter().collect::<String>(), "bc");
+}
+
+#[test]
+fn test_apply_with_skips_noops() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let insert = site1.append('a');
+    let delete = site1.delete_index(0).unwrap();
+
+    let mut events = Vec::new();
+    site2.apply_with(insert.clone(), |e| events.push(e));
+    site2.apply_with(insert, |e| events.push(e));
+    site2.apply_with(delete.clone(), |e| events.push(e));
+    site2.apply_with(delete, |e| events.push(e));
+
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn test_position_of() {
+    let mut site1 = LSeq::new(0);
+    for c in "abcde".chars() {
+        site1.append(c);
+    }
+    let mut site2 = LSeq::new(1);
+    let other_op = site2.append('z');
+
+    for (ix, entry) in site1.iter_entries().enumerate() {
+        assert_eq!(site1.position_of(&entry.id), Some(ix));
+        assert_eq!(site1.find_entry(&entry.id), Some(entry));
+    }
+
+    assert_eq!(site1.position_of(other_op.id()), None);
+    assert_eq!(site1.find_entry(other_op.id()), None);
+}
+
+#[test]
+fn test_validate() {
+    let mut site1 = LSeq::new(0);
+    assert_eq!(site1.validate(), Ok(()));
+
+    site1.append('a');
+    site1.append('c');
+    site1.insert_index(1, 'b');
+    site1.insert_index(0, 'z');
+    site1.delete_index(2);
+
+    assert_eq!(site1.validate(), Ok(()));
+}
+
+#[test]
+fn test_move_to() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    for c in "abc".chars() {
+        site2.apply(site1.append(c));
+    }
+
+    let ops = site1.move_to(0, 2);
+    assert_eq!(ops.len(), 2);
+    assert_eq!(site1.iter().collect::<String>(), "bca");
+
+    for op in ops {
+        site2.apply(op);
+    }
+    assert_eq!(site2.iter().collect::<String>(), "bca");
+
+    assert!(site1.move_to(3, 0).is_empty());
+    assert_eq!(site1.iter().collect::<String>(), "bca");
+}
+
+#[test]
+fn test_first_and_last() {
+    let mut site1 = LSeq::new(0);
+    assert_eq!(site1.first(), None);
+    assert_eq!(site1.last(), None);
+    assert!(site1.first_entry().is_none());
+    assert!(site1.last_entry().is_none());
+
+    site1.append('b');
+    site1.append('c');
+    site1.insert_index(0, 'a');
+
+    assert_eq!(site1.first(), Some(&'a'));
+    assert_eq!(site1.last(), Some(&'c'));
+    assert_eq!(site1.first_entry().map(|e| e.val), Some('a'));
+    assert_eq!(site1.last_entry().map(|e| e.val), Some('c'));
+}
+
 #[test]
 fn test_worst_case_inserts() {
     // by inserting always at the middle of the array, we grow the exponential tree beyond the
@@ -137,11 +266,13 @@ fn test_insert_followed_by_deletes() {
         let insert_op = site1.insert_index(ix, c);
         site2.apply(insert_op);
 
-        let delete_op =
-            site2
-                .delete_index(ix)
-                .expect(&format!("ix@{} was out of bounds@{}", ix, site2.len()));
+        assert!(site2.is_identifier_sorted());
+
+        let delete_op = site2
+            .delete_index(ix)
+            .unwrap_or_else(|| panic!("ix@{} was out of bounds@{}", ix, site2.len()));
         site1.apply(delete_op);
+        assert!(site1.is_identifier_sorted());
     }
 
     assert!(
@@ -186,6 +317,871 @@ fn test_mutual_insert_qc1() {
     );
 }
 
+#[test]
+fn test_push_bounded_slides_window() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    for c in "abc".chars() {
+        let ops = site1.push_bounded(c, 3);
+        assert_eq!(ops.len(), 1);
+        ops.into_iter().for_each(|op| site2.apply(op));
+    }
+
+    for c in "de".chars() {
+        let ops = site1.push_bounded(c, 3);
+        assert_eq!(ops.len(), 2);
+        ops.into_iter().for_each(|op| site2.apply(op));
+    }
+
+    assert_eq!(site1.iter().collect::<String>(), "cde");
+    assert_eq!(site2.iter().collect::<String>(), "cde");
+}
+
+#[cfg(feature = "streaming")]
+#[test]
+fn test_streaming_round_trip() {
+    let mut site = LSeq::new(0u32);
+    for i in 0..100_000u64 {
+        site.append(i);
+    }
+    site.delete_index(500);
+
+    let mut bytes = Vec::new();
+    site.serialize_streaming(&mut bytes).unwrap();
+    let decoded: LSeq<u64, u32> = LSeq::deserialize_streaming(&mut bytes.as_slice()).unwrap();
+
+    assert_eq!(decoded.len(), site.len());
+    assert!(decoded.iter_entries().eq(site.iter_entries()));
+    assert_eq!(decoded.clock(), site.clock());
+
+    // the decoded sequence picks up where the original left off
+    let mut decoded = decoded;
+    assert_eq!(decoded.append(7).dot(), &Dot::new(0, 100_002));
+}
+
+#[cfg(feature = "streaming")]
+#[test]
+fn test_streaming_truncated_input() {
+    let mut site = LSeq::new(0u32);
+    site.append('a');
+    site.append('b');
+
+    let mut bytes = Vec::new();
+    site.serialize_streaming(&mut bytes).unwrap();
+    bytes.pop();
+
+    let err = LSeq::<char, u32>::deserialize_streaming(&mut bytes.as_slice())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_deterministic_allocation_is_reproducible() {
+    let edit = |site: &mut LSeq<char, u32>| {
+        vec![
+            site.append('a'),
+            site.append('c'),
+            site.insert_index(1, 'b'),
+            site.insert_index(0, 'z'),
+        ]
+    };
+
+    let mut site1 = LSeq::deterministic(0);
+    let mut site2 = LSeq::deterministic(0);
+    assert_eq!(edit(&mut site1), edit(&mut site2));
+    assert_eq!(site1.iter().collect::<String>(), "zabc");
+}
+
+#[test]
+fn test_delete_with_element_scoped_rm_ctx() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    site2.apply(site1.append('a'));
+    site2.apply(site1.append('b'));
+
+    let id_a = site2.first_entry().unwrap().id.clone();
+    let id_b = site2.last_entry().unwrap().id.clone();
+    let ctx_a = site2.rm_ctx_for(&id_a).unwrap();
+    assert_eq!(ctx_a.clock, VClock::from(Dot::new(0, 1)));
+
+    // the context of `a` has not seen the insertion of `b`
+    assert_eq!(site2.delete_with_ctx(&id_b, ctx_a.clone()), None);
+
+    let op = site2.delete_with_ctx(&id_a, ctx_a).unwrap();
+    site1.apply(op);
+    assert_eq!(site1.iter().collect::<String>(), "b");
+    assert_eq!(site2.iter().collect::<String>(), "b");
+    assert!(site2.rm_ctx_for(&id_a).is_none());
+}
+
+#[test]
+fn test_apply_verified_rejects_forged_dots() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let valid = site1.append('a');
+    let forged = match site1.append('b') {
+        Op::Insert { id, val, .. } => Op::Insert {
+            id,
+            dot: Dot::new(0, 100),
+            val,
+        },
+        _ => unreachable!(),
+    };
+
+    let frontier = site1.clock().clone();
+    assert!(site2.apply_verified(valid, &frontier));
+    assert!(!site2.apply_verified(forged, &frontier));
+    assert_eq!(site2.iter().collect::<String>(), "a");
+}
+
+#[test]
+fn test_chunks() {
+    let mut site = LSeq::new(0);
+    for i in 0..10 {
+        site.append(i);
+    }
+
+    let chunks: Vec<_> = site.chunks(4).collect();
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(),
+        vec![4, 4, 2]
+    );
+
+    let reassembled: Vec<_> = chunks.into_iter().flatten().map(|e| e.val).collect();
+    assert_eq!(reassembled, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_apply_batch_reorders_ops() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let mut ops = vec![site1.append('a'), site1.append('b'), site1.append('c')];
+    ops.push(site1.delete_index(0).unwrap());
+    ops.reverse();
+
+    assert_eq!(site2.apply_batch(ops), vec![]);
+    assert_eq!(site2.iter().collect::<String>(), "bc");
+    assert_eq!(
+        site2.iter().collect::<String>(),
+        site1.iter().collect::<String>()
+    );
+
+    let mut site3 = LSeq::new(2);
+    let orphan = site1.delete_index(0).unwrap();
+    assert_eq!(site3.apply_batch(vec![orphan.clone()]), vec![orphan]);
+    assert!(site3.is_empty());
+}
+
+#[test]
+fn test_insert_all_after() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    for c in "abc".chars() {
+        site2.apply(site1.append(c));
+    }
+
+    let anchor = site1.first_entry().unwrap().id.clone();
+    for op in site1.insert_all_after(Some(&anchor), &['X', 'Y', 'Z']) {
+        site2.apply(op);
+    }
+    assert_eq!(site1.iter().collect::<String>(), "aXYZbc");
+    assert_eq!(site2.iter().collect::<String>(), "aXYZbc");
+
+    site1.insert_all_after(None, &['<', '>']);
+    assert_eq!(site1.iter().collect::<String>(), "<>aXYZbc");
+
+    // the block lands where a deleted anchor used to be
+    site1.delete_index(2);
+    site1.insert_all_after(Some(&anchor), &['!']);
+    assert_eq!(site1.iter().collect::<String>(), "<>!XYZbc");
+}
+
+#[test]
+fn test_insert_next_to_concurrently_deleted_element() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    for c in "abc".chars() {
+        site2.apply(site1.append(c));
+    }
+
+    // site2 deletes 'b' while site1 inserts on both sides of it
+    let delete = site2.delete_index(1).unwrap();
+    let before = site1.insert_index(1, 'X');
+    let after = site1.insert_index(3, 'Y');
+
+    site1.apply(delete);
+    site2.apply(before);
+    site2.apply(after);
+
+    assert_eq!(site1.iter().collect::<String>(), "aXYc");
+    assert_eq!(site2.iter().collect::<String>(), "aXYc");
+}
+
+#[test]
+fn test_repeated_inserts_at_the_front_and_between_neighbours() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    for c in "edcba".chars() {
+        site2.apply(site1.insert_index(0, c));
+    }
+    for c in "1234".chars() {
+        site2.apply(site1.insert_index(1, c));
+    }
+
+    for site in [&site1, &site2].iter() {
+        assert_eq!(site.iter().collect::<String>(), "a4321bcde");
+        assert_eq!(site.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_columnar_round_trip() {
+    let mut site = LSeq::new(0u32);
+    for c in "hello world".chars() {
+        site.append(c);
+    }
+    site.delete_index(5);
+
+    let columns = site.to_columnar();
+    assert_eq!(columns.vals.iter().collect::<String>(), "helloworld");
+
+    let json = serde_json::to_string(&columns).unwrap();
+    let decoded = LSeq::from_columnar(serde_json::from_str(&json).unwrap()).unwrap();
+    assert!(decoded.iter_entries().eq(site.iter_entries()));
+    assert_eq!(decoded.clock(), site.clock());
+
+    let mut columns = columns;
+    columns.vals.pop();
+    assert_eq!(
+        LSeq::from_columnar(columns).err(),
+        Some("columns have different lengths".to_string())
+    );
+}
+
+#[test]
+fn test_deserialize_format_without_clock() {
+    let mut site = LSeq::new(0u32);
+    for c in "abc".chars() {
+        site.append(c);
+    }
+    site.delete_index(1);
+
+    // strip the fields that were added after the first format
+    let mut json = serde_json::to_value(&site).unwrap();
+    let fields = json.as_object_mut().unwrap();
+    for field in ["clock", "applied_ops", "last_applied"].iter() {
+        assert!(fields.remove(*field).is_some());
+    }
+    let gen = fields["gen"].as_object_mut().unwrap();
+    for field in ["deterministic", "bias"].iter() {
+        assert!(gen.remove(*field).is_some());
+    }
+
+    let mut decoded: LSeq<char, u32> = serde_json::from_value(json).unwrap();
+    assert!(decoded.iter_entries().eq(site.iter_entries()));
+    assert!(decoded.clock().is_empty());
+    assert_eq!(decoded.applied_op_count(), 0);
+    assert_eq!(decoded.last_applied_dot(), None);
+
+    // the local dot survives, fresh ops don't reuse the dots of the old ones
+    assert_eq!(decoded.append('d').dot(), site.append('d').dot());
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_read_small_matches_entries() {
+    let mut site = LSeq::new(0);
+    for c in "abc".chars() {
+        site.append(c);
+    }
+
+    let small = site.read_small();
+    assert!(!small.spilled());
+    assert!(small
+        .iter()
+        .map(|(id, val)| (*id, *val))
+        .eq(site.iter_entries().map(|e| (&e.id, &e.val))));
+    drop(small);
+
+    for c in "defghijk".chars() {
+        site.append(c);
+    }
+    assert!(site.read_small().spilled());
+    assert_eq!(
+        site.read_small()
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect::<String>(),
+        "abcdefghijk"
+    );
+}
+
+#[test]
+fn test_applied_op_count_and_last_dot() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    assert_eq!(site2.applied_op_count(), 0);
+    assert_eq!(site2.last_applied_dot(), None);
+
+    let ops = vec![
+        site1.append('a'),
+        site1.append('b'),
+        site1.delete_index(0).unwrap(),
+    ];
+    for op in ops {
+        site2.apply(op);
+    }
+
+    assert_eq!(site1.applied_op_count(), 3);
+    assert_eq!(site2.applied_op_count(), site1.applied_op_count());
+    assert_eq!(site2.last_applied_dot(), Some(&Dot::new(0, 3)));
+
+    site2.append('c');
+    assert_eq!(site2.applied_op_count(), 4);
+    assert_eq!(site2.last_applied_dot(), Some(&Dot::new(1, 1)));
+}
+
+#[test]
+fn test_applied_op_count_is_ignored_by_eq() {
+    let mut author = LSeq::new(0);
+    let ops = [author.append('a'), author.append('b')];
+
+    let mut site1 = LSeq::new(1);
+    let mut site2 = LSeq::new(1);
+    for op in ops.iter().cloned() {
+        site1.apply(op);
+    }
+    // delivered in reverse order, and the first op twice
+    for op in ops.iter().rev().chain(ops.iter().take(1)).cloned() {
+        site2.apply(op);
+    }
+
+    assert_ne!(site1.applied_op_count(), site2.applied_op_count());
+    assert_ne!(site1.last_applied_dot(), site2.last_applied_dot());
+    assert!(site1 == site2);
+    assert_eq!(site1.partial_cmp(&site2), Some(std::cmp::Ordering::Equal));
+
+    let hash = |seq: &LSeq<char, u8>| {
+        let mut hasher = DefaultHasher::new();
+        seq.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&site1), hash(&site2));
+}
+
+#[test]
+fn test_neighbors() {
+    let mut site = LSeq::new(0);
+    for c in "abc".chars() {
+        site.append(c);
+    }
+    let ids: Vec<_> = site.iter_entries().map(|e| e.id.clone()).collect();
+
+    assert_eq!(site.neighbors(0), (None, Some(&ids[0])));
+    assert_eq!(site.neighbors(1), (Some(&ids[0]), Some(&ids[1])));
+    assert_eq!(site.neighbors(3), (Some(&ids[2]), None));
+    assert_eq!(site.neighbors(10), (Some(&ids[2]), None));
+    assert_eq!(LSeq::<char, u8>::new(0).neighbors(0), (None, None));
+}
+
+#[test]
+fn test_apply_checked_rejects_unknown_deletes() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let insert = site1.append('a');
+    let delete = site1.delete_index(0).unwrap();
+
+    // delivered out of order, the strict path reports the delete
+    assert_eq!(
+        site2.apply_checked(delete.clone()),
+        Err(Error::UnknownIdentifier)
+    );
+    assert_eq!(site2.apply_checked(insert.clone()), Ok(()));
+    assert_eq!(site2.apply_checked(delete.clone()), Ok(()));
+    assert!(site2.is_empty());
+
+    // while the lenient path ignores it
+    let mut site3 = LSeq::new(2);
+    site3.apply(delete);
+    site3.apply(insert);
+    assert_eq!(site3.iter().collect::<String>(), "a");
+}
+
+#[test]
+fn test_has_same_values() {
+    let mut site1 = LSeq::new(0);
+    for c in "abc".chars() {
+        site1.append(c);
+    }
+
+    let mut site2 = LSeq::new(1);
+    site2.append('c');
+    site2.insert_index(0, 'a');
+    site2.insert_index(1, 'x');
+    site2.insert_index(1, 'b');
+    site2.delete_index(2);
+
+    assert!(site1 != site2);
+    assert!(site1.has_same_values(&site2));
+
+    site2.append('d');
+    assert!(!site1.has_same_values(&site2));
+}
+
+#[test]
+fn test_undo_redo() {
+    let mut doc = History::new(0, 10);
+    let mut remote = LSeq::new(1);
+    let text = |doc: &History<char, u8>| doc.lseq().iter().collect::<String>();
+
+    for (ix, c) in "abc".chars().enumerate() {
+        remote.apply(doc.insert_index(ix, c));
+    }
+    remote.apply(doc.delete_index(1).unwrap());
+    assert_eq!(text(&doc), "ac");
+
+    let mut sync =
+        |ops: Option<Vec<Op<char, u8>>>| ops.unwrap().into_iter().for_each(|op| remote.apply(op));
+
+    sync(doc.undo());
+    assert_eq!(text(&doc), "abc");
+    sync(doc.undo());
+    assert_eq!(text(&doc), "ab");
+    sync(doc.redo());
+    assert_eq!(text(&doc), "abc");
+    sync(doc.redo());
+    assert_eq!(text(&doc), "ac");
+    assert!(doc.redo().is_none());
+
+    sync(doc.undo());
+    assert_eq!(text(&doc), "abc");
+    assert_eq!(remote.iter().collect::<String>(), "abc");
+
+    // a new edit clears the redo history and remote ops are not recorded
+    doc.insert_index(0, 'z');
+    assert!(doc.redo().is_none());
+    doc.apply(remote.append('r'));
+    doc.undo();
+    assert_eq!(text(&doc), "abcr");
+}
+
+#[test]
+fn test_undo_history_is_bounded() {
+    let mut doc = History::new(0, 2);
+    for c in "abc".chars() {
+        doc.insert_index(0, c);
+    }
+    assert!(doc.undo().is_some());
+    assert!(doc.undo().is_some());
+    assert!(doc.undo().is_none());
+    assert_eq!(doc.lseq().iter().collect::<String>(), "a");
+}
+
+#[test]
+fn test_front_inserts_grow_average_depth() {
+    let mut site = LSeq::deterministic(0);
+    assert_eq!(site.average_depth(), 0.0);
+
+    site.insert_index(0, 'a');
+    let shallow = site.average_depth();
+    assert_eq!(shallow, 1.0);
+
+    for _ in 0..50 {
+        site.insert_index(0, 'a');
+    }
+    let front = site.first_entry().unwrap().id.clone();
+    assert!(site.depth_of(&front) > 1);
+    assert!(site.average_depth() > shallow);
+}
+
+#[test]
+fn test_merge_ops_of_overlapping_logs() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+
+    let log1 = vec![site1.append('a'), site1.append('b'), site1.append('c')];
+    for op in log1.iter().cloned() {
+        site2.apply(op);
+    }
+    // site2 deletes an insert with a larger counter than its own delete
+    let mut log2 = log1[1..].to_vec();
+    log2.push(site2.delete_index(2).unwrap());
+    log2.push(site2.append('x'));
+    for op in log2[2..].iter().cloned() {
+        site1.apply(op);
+    }
+
+    let merged = LSeq::merge_ops(log1.clone(), log2.clone());
+    assert_eq!(merged.len(), 5);
+    assert_eq!(merged, LSeq::merge_ops(log2, log1.clone()));
+    let insert_of_c = merged.iter().position(|op| op == &log1[2]).unwrap();
+    let delete_of_c = merged
+        .iter()
+        .position(|op| matches!(op, Op::Delete { .. }))
+        .unwrap();
+    assert!(insert_of_c < delete_of_c);
+
+    let mut replayed = LSeq::new(2);
+    for op in merged {
+        replayed.apply(op);
+    }
+    assert_eq!(replayed.iter().collect::<String>(), "abx");
+    assert!(replayed.has_same_values(&site1));
+    assert!(replayed.has_same_values(&site2));
+}
+
+#[test]
+fn test_read_split_clocks() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    site2.apply(site1.append('a'));
+    site1.apply(site2.append('x'));
+    site2.apply(site1.delete_index(1).unwrap());
+
+    let read = site2.read_split();
+    assert_eq!(read.val.len(), 1);
+    assert_eq!(read.val[0].1, 'a');
+    assert_eq!(read.add_clock, VClock::from_slice(&[(0, 2), (1, 1)]));
+    assert_eq!(read.rm_clock, VClock::from_slice(&[(0, 1)]));
+
+    let (id, _) = &read.val[0];
+    let ctx = read.derive_rm_ctx();
+    assert!(site2.delete_with_ctx(id, ctx).is_some());
+}
+
+#[test]
+fn test_iter_with_clocks() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    site2.apply(site1.append('a'));
+    site1.apply(site2.append('b'));
+    site2.apply(site1.append('c'));
+
+    let elements: Vec<_> = site1.iter_with_clocks().collect();
+    assert_eq!(elements.len(), 3);
+    for (id, val, clock) in elements {
+        let entry = site1.find_entry(id).unwrap();
+        assert_eq!(&entry.val, val);
+        assert!(clock.has_seen(&entry.dot));
+    }
+}
+
+#[test]
+fn test_compact_step_shortens_identifiers() {
+    let mut site1 = LSeq::deterministic(0);
+    let mut site2 = LSeq::new(1);
+    for i in 0..30 {
+        site2.apply(site1.insert_index(0, i));
+    }
+    while site1.len() > 3 {
+        site2.apply(site1.delete_index(1).unwrap());
+    }
+    let values: Vec<_> = site1.iter().cloned().collect();
+    let max_depth = |seq: &LSeq<i32, i32>| seq.iter_entries().map(|e| e.id.depth()).max();
+    let before = max_depth(&site1);
+
+    let mut steps = 0;
+    while let Some(ops) = site1.compact_step() {
+        assert_eq!(ops.len(), 2);
+        for op in ops {
+            site2.apply(op);
+        }
+        steps += 1;
+        assert!(steps <= 3);
+    }
+
+    assert!(steps > 0);
+    assert!(max_depth(&site1) < before);
+    assert_eq!(site1.iter().cloned().collect::<Vec<_>>(), values);
+    assert_eq!(site1.validate(), Ok(()));
+    assert_eq!(site2.iter().cloned().collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn test_split_at() {
+    let mut site = LSeq::new(0);
+    for c in "hello world".chars() {
+        site.append(c);
+    }
+    let id = site.iter_entries().nth(5).unwrap().id.clone();
+
+    let mut tail = site.split_at(&id).unwrap();
+    assert_eq!(site.iter().collect::<String>(), "hello");
+    assert_eq!(tail.iter().collect::<String>(), " world");
+    assert!(site.is_identifier_sorted());
+    assert!(tail.is_identifier_sorted());
+    assert_eq!(tail.first_entry().unwrap().id, id);
+
+    assert_eq!(site.split_at(&id).err(), Some(Error::UnknownIdentifier));
+    assert_eq!(site.len(), 5);
+    tail.append('!');
+    assert_eq!(tail.iter().collect::<String>(), " world!");
+}
+
+#[test]
+fn test_read_into_reuses_buffer() {
+    let mut site = LSeq::new(0);
+    for c in "abcdef".chars() {
+        site.append(c);
+    }
+
+    let mut buf = Vec::new();
+    site.read_into(&mut buf);
+    assert_eq!(buf, site.read_split().val);
+    let capacity = buf.capacity();
+
+    site.delete_index(0);
+    for _ in 0..10 {
+        site.read_into(&mut buf);
+        assert_eq!(buf.capacity(), capacity);
+    }
+    assert_eq!(buf, site.read_split().val);
+}
+
+#[test]
+fn test_remote_ops_build_byte_identical_trees() {
+    let mut author = LSeq::new(0);
+    let mut ops = Vec::new();
+    for (i, c) in "remote".chars().enumerate() {
+        ops.push(author.insert_index(i / 2, c));
+    }
+    ops.push(author.delete_index(3).unwrap());
+
+    let mut site1 = LSeq::new(1);
+    let mut site2 = LSeq::new(2);
+    for op in ops {
+        site1.apply_remote(op.clone());
+        site2.apply_remote(op);
+    }
+
+    let entries = |seq: &LSeq<char, u8>| {
+        let entries: Vec<_> = seq.iter_entries().collect();
+        bincode::serialize(&entries).unwrap()
+    };
+    assert_eq!(entries(&site1), entries(&site2));
+    assert_eq!(entries(&site1), entries(&author));
+}
+
+#[test]
+fn test_read_since() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    site2.apply(site1.append('a'));
+    site2.apply(site1.append('b'));
+
+    assert_eq!(site2.seq_no(), 2);
+    let version = site2.clock().clone();
+
+    let newer = vec![site1.append('c'), site1.insert_index(0, 'd')];
+    for op in newer.iter().cloned() {
+        site2.apply(op);
+    }
+
+    let (ops, latest) = site2.read_since(&version);
+    assert_eq!(site2.seq_no(), 4);
+    assert_eq!(&latest, site2.clock());
+    assert_eq!(ops.len(), 2);
+    for op in newer {
+        assert!(ops.contains(&op));
+    }
+
+    let (ops, _) = site2.read_since(&latest);
+    assert!(ops.is_empty());
+}
+
+#[test]
+fn test_read_since_with_two_writers() {
+    let mut site1 = LSeq::new(0);
+    let mut site2 = LSeq::new(1);
+    for c in "abcde".chars() {
+        site2.apply(site1.append(c));
+        let op = site2.append(c.to_ascii_uppercase());
+        site1.apply(op);
+    }
+    assert_eq!(site1.seq_no(), 10);
+    let version = site1.clock().clone();
+
+    // each writer's new counter (6) is below the total of the clock (10)
+    let local = site1.append('f');
+    let remote = site2.insert_index(0, 'F');
+    site1.apply(remote.clone());
+
+    let (ops, _) = site1.read_since(&version);
+    assert_eq!(ops, vec![remote, local]);
+}
+
+#[test]
+fn test_replayed_ops_are_placed_identically() {
+    let mut origin = LSeq::new(0);
+    let mut log = vec![origin.append('a'), origin.append('c')];
+    log.push(origin.insert_index(1, 'b'));
+    log.extend(origin.delete_index(0));
+
+    let mut replica1 = LSeq::new(1);
+    let mut replica2 = LSeq::new(2);
+    for op in log.iter().cloned() {
+        replica1.apply(op);
+    }
+    for op in log.into_iter() {
+        replica2.apply(op);
+    }
+
+    let entries1: Vec<_> = replica1.iter_entries().collect();
+    let entries2: Vec<_> = replica2.iter_entries().collect();
+    assert_eq!(entries1, entries2);
+    assert_eq!(entries1, origin.iter_entries().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_delete_ignores_reinserted_identifier() {
+    let mut site1 = LSeq::new(0);
+    let insert = site1.append('x');
+    let delete = site1.delete_index(0).unwrap();
+
+    // the identifier is reused by a later insertion
+    let reinsert = Op::Insert {
+        id: insert.id().clone(),
+        dot: Dot::new(0, 3),
+        val: 'y',
+    };
+    site1.apply(reinsert);
+
+    // a concurrent delete of the original insertion must not remove the new element
+    site1.apply(delete);
+    assert_eq!(site1.iter().collect::<String>(), "y");
+}
+
+#[test]
+fn test_replicas_gossiping_converge() {
+    let mut rng = rand::thread_rng();
+    let mut replicas: Vec<Replica<char, SiteId>> = (0..3).map(Replica::new).collect();
+
+    for round in 0..100 {
+        for replica in replicas.iter_mut() {
+            let len = replica.lseq().len();
+            if len > 0 && rng.gen_ratio(1, 3) {
+                assert!(replica.local_delete(rng.gen_range(0, len)));
+            } else {
+                replica.local_insert(rng.gen_range(0, len + 1), rng.sample(Alphanumeric));
+            }
+        }
+
+        // Only gossip every few rounds so that ops pile up and get reordered.
+        if round % 5 == 4 {
+            let outgoing: Vec<_> = replicas.iter_mut().map(|r| r.outgoing_ops()).collect();
+            for (i, replica) in replicas.iter_mut().enumerate() {
+                let mut ops: Vec<_> = outgoing
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .flat_map(|(_, ops)| ops.iter().cloned())
+                    .collect();
+                ops.shuffle(&mut rng);
+                // deliver in two halves to exercise buffering across calls
+                let rest = ops.split_off(ops.len() / 2);
+                replica.receive(ops);
+                replica.receive(rest);
+            }
+        }
+    }
+
+    for replica in replicas.iter() {
+        assert_eq!(replica.pending_ops().count(), 0);
+        assert_eq!(replica.clock(), replicas[0].clock());
+        assert_eq!(
+            replica.lseq().iter().collect::<String>(),
+            replicas[0].lseq().iter().collect::<String>()
+        );
+    }
+}
+
+#[test]
+fn test_replica_buffers_reversed_backlog() {
+    let mut author = Replica::new(0);
+    for i in 0..2000 {
+        author.local_insert(i, i);
+    }
+    let mut ops = author.outgoing_ops();
+    ops.reverse();
+
+    // every op waits on the first one
+    let mut replica = Replica::new(1);
+    let first = ops.pop().unwrap();
+    replica.receive(ops);
+    assert_eq!(replica.pending_ops().count(), 1999);
+    assert!(replica.lseq().is_empty());
+
+    // redelivering a pending op doesn't queue it twice
+    let redelivered = replica.pending_ops().next().unwrap().clone();
+    replica.receive(vec![redelivered]);
+    assert_eq!(replica.pending_ops().count(), 1999);
+
+    replica.receive(vec![first]);
+    assert_eq!(replica.pending_ops().count(), 0);
+    assert_eq!(replica.clock(), author.clock());
+    assert!(replica.lseq().iter().eq(author.lseq().iter()));
+}
+
+#[test]
+fn test_replicas_converge_under_reordering_and_redelivery() {
+    // each seed is a reproducible run, a failure reports the seed it happened with
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut replicas: Vec<Replica<char, SiteId>> = (0..4).map(Replica::new).collect();
+        let mut log = Vec::new();
+
+        for _ in 0..30 {
+            for replica in replicas.iter_mut() {
+                for _ in 0..rng.gen_range(0, 4) {
+                    let len = replica.lseq().len();
+                    if len > 0 && rng.gen_ratio(1, 3) {
+                        assert!(replica.local_delete(rng.gen_range(0, len)));
+                    } else {
+                        let ix = rng.gen_range(0, len + 1);
+                        replica.local_insert(ix, rng.sample(Alphanumeric));
+                    }
+                }
+                log.extend(replica.outgoing_ops());
+            }
+
+            // deliver a random sample of everything sent so far, duplicates included
+            for replica in replicas.iter_mut() {
+                let count = rng.gen_range(0, log.len() + 1);
+                let ops: Vec<_> = (0..count)
+                    .map(|_| log.choose(&mut rng).unwrap().clone())
+                    .collect();
+                replica.receive(ops);
+            }
+        }
+
+        for replica in replicas.iter_mut() {
+            let mut ops = log.clone();
+            ops.extend(log.choose_multiple(&mut rng, log.len() / 2).cloned());
+            ops.shuffle(&mut rng);
+            replica.receive(ops);
+        }
+
+        let expected: String = replicas[0].lseq().iter().collect();
+        for replica in replicas.iter() {
+            assert_eq!(replica.pending_ops().count(), 0, "seed {}", seed);
+            assert_eq!(replica.clock(), replicas[0].clock(), "seed {}", seed);
+            assert_eq!(
+                replica.lseq().iter().collect::<String>(),
+                expected,
+                "replicas diverged with seed {}",
+                seed
+            );
+            assert!(replica.lseq().is_identifier_sorted(), "seed {}", seed);
+        }
+    }
+}
+
 quickcheck! {
     fn prop_mutual_inserting(plan: Vec<(u8, usize, bool)>) -> bool {
         let mut site0 = LSeq::new(0);
@@ -203,7 +1199,7 @@ quickcheck! {
         }
 
         assert_eq!(site0.iter().collect::<Vec<_>>(), site1.iter().collect::<Vec<_>>());
-        true
+        site0.is_identifier_sorted() && site1.is_identifier_sorted()
     }
 
     fn prop_inserts_and_deletes(op1: OperationList, op2: OperationList) -> TestResult {
@@ -243,7 +1239,7 @@ quickcheck! {
         let site1_text = site1.iter().collect::<String>();
         let site2_text = site2.iter().collect::<String>();
 
-        TestResult::from_bool(site1_text == site2_text)
+        TestResult::from_bool(site1_text == site2_text && site1.is_identifier_sorted())
     }
 
     fn prop_ops_are_idempotent(ops: OperationList) -> TestResult {
@@ -6,8 +6,9 @@ type TKey = u8;
 type TVal = MVReg<u8, TActor>;
 type TOp = map::Op<TKey, Map<TKey, TVal, TActor>, TActor>;
 type TMap = Map<TKey, Map<TKey, TVal, TActor>, TActor>;
+type TOpPrims = (u8, Vec<(u8, u8, u8, u8, u8)>);
 
-fn build_ops(prims: (u8, Vec<(u8, u8, u8, u8, u8)>)) -> (TActor, Vec<TOp>) {
+fn build_ops(prims: TOpPrims) -> (TActor, Vec<TOp>) {
     let (actor, ops_data) = prims;
 
     let mut ops = Vec::new();
@@ -522,8 +523,8 @@ fn apply_ops(map: &mut TMap, ops: &[TOp]) {
 quickcheck! {
     // TODO: add test to show equivalence of merge and Op exchange
     fn prop_op_exchange_same_as_merge(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -548,8 +549,8 @@ quickcheck! {
     }
 
     fn prop_op_exchange_converges(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -576,9 +577,9 @@ quickcheck! {
     }
 
     fn prop_op_exchange_associative(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops3_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims,
+        ops3_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -609,7 +610,7 @@ quickcheck! {
     }
 
     fn prop_op_idempotent(
-        ops_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops_prim: TOpPrims
     ) -> bool {
         let ops = build_ops(ops_prim);
         let mut m = TMap::new();
@@ -622,9 +623,9 @@ quickcheck! {
     }
 
     fn prop_op_associative(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops3_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims,
+        ops3_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -656,9 +657,9 @@ quickcheck! {
 
 
     fn prop_merge_associative(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops3_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims,
+        ops3_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -690,8 +691,8 @@ quickcheck! {
     }
 
     fn prop_merge_commutative(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -719,8 +720,8 @@ quickcheck! {
 
 
     fn prop_merge_followed_by_merge(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
         let ops2 = build_ops(ops2_prim);
@@ -746,7 +747,7 @@ quickcheck! {
     }
 
     fn prop_merge_idempotent(
-        ops_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops_prim: TOpPrims
     ) -> bool {
         let ops = build_ops(ops_prim);
 
@@ -762,7 +763,7 @@ quickcheck! {
     }
 
     fn prop_forget_with_empty_vclock_is_nop(
-        ops_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops_prim: TOpPrims
     ) -> bool {
         let ops = build_ops(ops_prim);
 
@@ -776,7 +777,7 @@ quickcheck! {
     }
 
     fn prop_forget_with_map_clock_is_empty_map(
-        ops_prim: (u8, Vec<(u8, u8, u8, u8, u8)>)
+        ops_prim: TOpPrims
     ) -> bool {
         let mut m = TMap::new();
         apply_ops(&mut m, &build_ops(ops_prim).1);
@@ -790,8 +791,8 @@ quickcheck! {
     }
 
     fn prop_forget_than_merge_same_as_merge_than_forget(
-        ops1_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
-        ops2_prim: (u8, Vec<(u8, u8, u8, u8, u8)>),
+        ops1_prim: TOpPrims,
+        ops2_prim: TOpPrims,
         vclock: VClock<u8>
     ) -> TestResult {
         let ops1 = build_ops(ops1_prim);
@@ -1,6 +1,10 @@
+use crdts::dot::DotRange;
+use crdts::vclock::{ClockComparer, ClockSet, DenseClock, DottedVClock, Relation, RetiringClock};
 use crdts::*;
+use serde::{Deserialize, Serialize};
 
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 quickcheck! {
     fn prop_into_iter_produces_same_vclock(clock: VClock<u8>) -> bool {
@@ -50,6 +54,96 @@ quickcheck! {
         a_glb == b_glb
     }
 
+    fn prop_merge_is_least_upper_bound(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let mut m = a.clone();
+        m.merge(b.clone());
+
+        // lowering any tracked actor of the merge gives a clock that is no longer an upper bound
+        let minimal = m.dots.iter().all(|(actor, counter)| {
+            let mut lowered = m.clone();
+            if *counter > 1 {
+                lowered.dots.insert(*actor, counter - 1);
+            } else {
+                lowered.dots.remove(actor);
+            }
+            !(lowered >= a && lowered >= b)
+        });
+
+        m >= a && m >= b && minimal
+    }
+
+    fn prop_glb_is_lower_bound(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let mut glb = a.clone();
+        glb.glb(&b);
+
+        glb <= a && glb <= b
+    }
+
+    fn prop_glb_all_is_lower_bound(clocks: Vec<VClock<u8>>) -> bool {
+        let glb = VClock::glb_all(clocks.clone());
+        clocks.iter().all(|clock| &glb <= clock)
+    }
+
+    fn prop_dot_order_does_not_matter(dots: Vec<Dot<u8>>) -> bool {
+        let collected: VClock<u8> = dots.iter().cloned().collect();
+        let reversed: VClock<u8> = dots.iter().rev().cloned().collect();
+
+        let mut sorted = dots;
+        sorted.sort_by_key(|dot| (dot.counter, dot.actor));
+        let mut extended = VClock::new();
+        extended.extend(sorted.iter().cloned());
+        extended.extend(sorted);
+
+        collected == reversed && collected == extended
+    }
+
+    fn prop_lub_is_upper_bound(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let lub = VClock::lub(&a, &b);
+        let mut merged = a.clone();
+        merged.merge(b.clone());
+
+        lub >= a && lub >= b && lub == merged && lub.dots.values().all(|c| *c > 0)
+    }
+
+    fn prop_add_assign_is_merge(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let mut added = a.clone();
+        added += &b;
+        let mut merged = a.clone();
+        merged.merge(b.clone());
+
+        added == merged && a.clone().merged(b.clone()) == merged
+    }
+
+    fn prop_retiring_add_assign_is_merge(
+        a: VClock<u8>,
+        b: VClock<u8>,
+        retired_a: Vec<bool>,
+        retired_b: Vec<bool>
+    ) -> bool {
+        // retire the actors of the clock picked by the mask
+        let retiring = |clock: VClock<u8>, mask: Vec<bool>| {
+            let actors: Vec<u8> = clock.dots.keys().cloned().collect();
+            let mut clock = RetiringClock::from(clock);
+            for (actor, retire) in actors.iter().zip(mask) {
+                if retire {
+                    clock.retire(actor);
+                }
+            }
+            clock
+        };
+        let a = retiring(a, retired_a);
+        let b = retiring(b, retired_b);
+
+        let mut added = a.clone();
+        added += &b;
+        let mut merged = a.clone();
+        merged.merge(b.clone());
+        let mut flipped = b;
+        flipped.merge(a);
+
+        added == merged && merged == flipped
+    }
+
     fn prop_forget_with_empty_is_nop(clock: VClock<u8>) -> bool {
         let mut subbed  = clock.clone();
         subbed.forget(&VClock::new());
@@ -67,28 +161,18 @@ quickcheck! {
         a.forget(&b);
 
         if a.is_empty() {
-            match a.partial_cmp(&b) {
-                Some(Ordering::Less) | Some(Ordering::Equal) => true,
-                _ => false
-            }
+            matches!(a.partial_cmp(&b), Some(Ordering::Less) | Some(Ordering::Equal))
         } else {
-            match a.partial_cmp(&b) {
-                None | Some(Ordering::Greater) => true,
-                _ => false
-            }
+            matches!(a.partial_cmp(&b), None | Some(Ordering::Greater))
         }
     }
 }
 
 #[test]
 fn test_forget() {
-    let mut a: VClock<u8> = vec![Dot::new(1, 4), Dot::new(2, 3), Dot::new(5, 9)]
-        .into_iter()
-        .collect();
-    let b: VClock<u8> = vec![Dot::new(1, 5), Dot::new(2, 3), Dot::new(5, 8)]
-        .into_iter()
-        .collect();
-    let expected: VClock<u8> = vec![Dot::new(5, 9)].into_iter().collect();
+    let mut a: VClock<u8> = VClock::from_slice(&[(1, 4), (2, 3), (5, 9)]);
+    let b: VClock<u8> = VClock::from_slice(&[(1, 5), (2, 3), (5, 8)]);
+    let expected: VClock<u8> = VClock::from_slice(&[(5, 9)]);
 
     a.forget(&b);
     assert_eq!(a, expected);
@@ -96,14 +180,12 @@ fn test_forget() {
 
 #[test]
 fn test_merge() {
-    let mut a: VClock<u8> = vec![Dot::new(1, 1), Dot::new(4, 4)].into_iter().collect();
-    let b: VClock<u8> = vec![Dot::new(3, 3), Dot::new(4, 3)].into_iter().collect();
+    let mut a: VClock<u8> = VClock::from_slice(&[(1, 1), (4, 4)]);
+    let b: VClock<u8> = VClock::from_slice(&[(3, 3), (4, 3)]);
 
     a.merge(b);
 
-    let expected: VClock<u8> = vec![Dot::new(1, 1), Dot::new(3, 3), Dot::new(4, 4)]
-        .into_iter()
-        .collect();
+    let expected: VClock<u8> = VClock::from_slice(&[(1, 1), (3, 3), (4, 4)]);
 
     assert_eq!(a, expected);
 }
@@ -209,3 +291,894 @@ fn test_vclock_ordering() {
     assert!(!(a > b));
     assert_eq!(a, b);
 }
+
+#[test]
+fn test_project() {
+    let clock: VClock<char> = VClock::from_slice(&[('A', 1), ('B', 2), ('C', 3)]);
+    let actors = vec!['A', 'C'].into_iter().collect();
+
+    let projected = clock.project(&actors);
+
+    let expected: VClock<char> = VClock::from_slice(&[('A', 1), ('C', 3)]);
+    assert_eq!(projected, expected);
+    assert_eq!(projected.get(&'B'), 0);
+}
+
+#[test]
+fn test_events() {
+    let clock: VClock<char> = VClock::from_slice(&[('A', 2), ('B', 1)]);
+
+    assert_eq!(
+        clock.events().collect::<Vec<_>>(),
+        vec![Dot::new('A', 1), Dot::new('A', 2), Dot::new('B', 1)]
+    );
+    assert_eq!(VClock::<char>::new().events().count(), 0);
+}
+
+#[test]
+fn test_contains_range() {
+    let range = DotRange::new('A', 2, 4);
+
+    let mut clock: VClock<char> = VClock::from_slice(&[('A', 3)]);
+    assert!(!clock.contains_range(&range));
+
+    for dot in Vec::from(range.clone()) {
+        clock.apply(dot);
+    }
+    assert!(clock.contains_range(&range));
+    assert_eq!(clock.get(&'A'), 4);
+}
+
+quickcheck! {
+    fn prop_dense_clock_agrees_with_vclock(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let round_trips = VClock::from(DenseClock::from(&a)) == a;
+
+        let mut merged = a.clone();
+        merged.merge(b.clone());
+        let mut dense_merged = DenseClock::from(&a);
+        dense_merged.merge(&DenseClock::from(&b));
+
+        let mut glb = a.clone();
+        glb.glb(&b);
+        let mut dense_glb = DenseClock::from(&a);
+        dense_glb.glb(&DenseClock::from(&b));
+
+        round_trips
+            && VClock::from(dense_merged) == merged
+            && dense_glb == DenseClock::from(&glb)
+    }
+
+    fn prop_compressed_bytes_round_trip(clock: VClock<u8>) -> bool {
+        VClock::from_compressed_bytes(&clock.compressed_bytes()) == Some(clock)
+    }
+
+    fn prop_to_sorted_vec_is_sorted_and_matches_iter(clock: VClock<u8>) -> bool {
+        let dots = clock.to_sorted_vec();
+        let sorted = dots.windows(2).all(|pair| pair[0].actor < pair[1].actor);
+        let matches_iter = dots
+            .iter()
+            .map(|dot| Dot::new(&dot.actor, dot.counter))
+            .eq(clock.iter());
+
+        sorted && matches_iter
+    }
+
+    fn prop_merge_dot_is_merge_of_single_dot_clock(clock: VClock<u8>, dot: Dot<u8>) -> bool {
+        let mut merged_dot = clock.clone();
+        merged_dot.merge_dot(dot);
+
+        let mut merged_clock = clock;
+        merged_clock.merge(VClock::from(dot));
+
+        merged_dot == merged_clock
+    }
+}
+
+#[test]
+fn test_merge_dot() {
+    let mut clock: VClock<char> = VClock::from_slice(&[('A', 2)]);
+
+    clock.merge_dot(Dot::new('A', 1));
+    assert_eq!(clock.get(&'A'), 2);
+
+    clock.merge_dot(Dot::new('A', 5));
+    clock.merge_dot(Dot::new('B', 1));
+    assert_eq!(clock.get(&'A'), 5);
+    assert_eq!(clock.get(&'B'), 1);
+}
+
+quickcheck! {
+    fn prop_clock_comparer_agrees_with_partial_cmp(clocks: Vec<VClock<u8>>) -> bool {
+        let mut comparer = ClockComparer::new();
+        for _ in 0..2 {
+            for a in clocks.iter() {
+                for b in clocks.iter() {
+                    if comparer.partial_cmp(a, b) != a.partial_cmp(b) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        let n = clocks.len();
+        comparer.comparisons() <= n * (n + 1) / 2
+    }
+}
+
+#[test]
+fn test_compact_json_round_trip() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(bound = "A: Serialize + serde::de::DeserializeOwned")]
+    struct Compact<A: Actor> {
+        #[serde(with = "crdts::vclock::compact")]
+        clock: VClock<A>,
+    }
+
+    let ints = Compact {
+        clock: vec![Dot::new(1u64, 4), Dot::new(7, 2)]
+            .into_iter()
+            .collect(),
+    };
+    let json = serde_json::to_string(&ints).unwrap();
+    assert_eq!(
+        json,
+        r#"{"clock":[{"actor":1,"counter":4},{"actor":7,"counter":2}]}"#
+    );
+    assert_eq!(serde_json::from_str::<Compact<u64>>(&json).unwrap(), ints);
+
+    let tuples = Compact {
+        clock: vec![Dot::new((1u64, 2u64), 3), Dot::new((4, 5), 6)]
+            .into_iter()
+            .collect(),
+    };
+    assert!(serde_json::to_string(&tuples.clock).is_err());
+    let json = serde_json::to_string(&tuples).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Compact<(u64, u64)>>(&json).unwrap(),
+        tuples
+    );
+}
+
+#[test]
+fn test_advance_all() {
+    let mut clock: VClock<char> = VClock::from_slice(&[('A', 1), ('B', 5)]);
+
+    clock.advance_all();
+
+    let expected: VClock<char> = VClock::from_slice(&[('A', 2), ('B', 6)]);
+    assert_eq!(clock, expected);
+    assert_eq!(clock.get(&'C'), 0);
+    assert!(!clock.dots.contains_key(&'C'));
+
+    let mut empty: VClock<char> = VClock::new();
+    empty.advance_all();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_advance_all_saturates() {
+    let mut clock: VClock<char> = VClock::from_slice(&[('A', u64::MAX), ('B', 5)]);
+
+    clock.advance_all();
+
+    assert_eq!(clock, VClock::from_slice(&[('A', u64::MAX), ('B', 6)]));
+}
+
+quickcheck! {
+    fn prop_clock_set_round_trips(clocks: Vec<VClock<u8>>) -> bool {
+        let set = ClockSet::new(clocks.clone());
+        set.len() == clocks.len()
+            && set.clocks().collect::<Vec<_>>() == clocks
+            && clocks.iter().all(|c| set.base() <= c)
+    }
+}
+
+#[test]
+fn test_clock_set_stores_deltas() {
+    let a: VClock<char> = VClock::from_slice(&[('A', 10), ('B', 7), ('C', 1)]);
+    let b: VClock<char> = VClock::from_slice(&[('A', 10), ('B', 8)]);
+    let c: VClock<char> = VClock::from_slice(&[('A', 12), ('B', 7)]);
+
+    let set = ClockSet::new(vec![a.clone(), b.clone(), c.clone()]);
+
+    let base: VClock<char> = VClock::from_slice(&[('A', 10), ('B', 7)]);
+    assert_eq!(set.base(), &base);
+    assert_eq!(
+        set.deltas(),
+        &[
+            VClock::from(Dot::new('C', 1)),
+            VClock::from(Dot::new('B', 8)),
+            VClock::from(Dot::new('A', 12)),
+        ]
+    );
+    assert_eq!(set.get(0), Some(a));
+    assert_eq!(set.get(1), Some(b));
+    assert_eq!(set.get(2), Some(c));
+    assert_eq!(set.get(3), None);
+
+    assert!(ClockSet::<char>::new(vec![]).is_empty());
+}
+
+#[test]
+fn test_partial_cmp_projected() {
+    let a: VClock<char> = VClock::from_slice(&[('A', 3), ('B', 1), ('C', 5)]);
+    let b: VClock<char> = VClock::from_slice(&[('A', 2), ('B', 1), ('C', 6)]);
+    assert!(a.concurrent(&b));
+
+    let ab = vec!['A', 'B'].into_iter().collect();
+    assert_eq!(a.partial_cmp_projected(&b, &ab), Some(Ordering::Greater));
+    assert_eq!(b.partial_cmp_projected(&a, &ab), Some(Ordering::Less));
+
+    let b_only = vec!['B'].into_iter().collect();
+    assert_eq!(a.partial_cmp_projected(&b, &b_only), Some(Ordering::Equal));
+
+    let all = vec!['A', 'B', 'C'].into_iter().collect();
+    assert_eq!(a.partial_cmp_projected(&b, &all), None);
+}
+
+#[test]
+fn test_retiring_clock_records_counter() {
+    let mut clock = RetiringClock::from(VClock::from_slice(&[(1u8, 4), (2, 2)]));
+
+    clock.retire(&1);
+    assert_eq!(clock.clock(), &VClock::from(Dot::new(2, 2)));
+    assert_eq!(clock.retired(), &VClock::from(Dot::new(1, 4)).dots);
+
+    // a stale message from the retired actor is still recognized as seen
+    assert!(clock.has_seen(&Dot::new(1, 3)));
+    assert!(clock.contains_range(&DotRange::from(Dot::new(1, 3))));
+    assert!(!clock.has_seen(&Dot::new(1, 5)));
+
+    // and applying it doesn't bring the actor back
+    clock.apply(Dot::new(1, 3));
+    assert_eq!(clock.clock(), &VClock::from(Dot::new(2, 2)));
+
+    // retiring an unknown actor is a no-op
+    clock.retire(&7);
+    assert_eq!(clock.retired(), &VClock::from(Dot::new(1, 4)).dots);
+}
+
+#[test]
+fn test_retiring_clock_merge() {
+    let mut a = RetiringClock::from(VClock::from_slice(&[(1u8, 4), (2, 2)]));
+    let b = RetiringClock::from(VClock::from_slice(&[(1, 3), (3, 1)]));
+    a.retire(&1);
+
+    let mut merged = b.clone();
+    merged.merge(a.clone());
+    a.merge(b);
+    assert_eq!(merged, a);
+    assert_eq!(a.clock(), &VClock::from_slice(&[(2, 2), (3, 1)]));
+    assert!(a.has_seen(&Dot::new(1, 4)));
+}
+
+#[test]
+fn test_vclock_bincode_layout_is_the_dots_map() {
+    // clocks encoded before any other field existed still decode
+    let clock = VClock::from_slice(&[(1u8, 4), (2, 2)]);
+    let bytes = bincode::serialize(&clock.dots).unwrap();
+    assert_eq!(bincode::serialize(&clock).unwrap(), bytes);
+    assert_eq!(bincode::deserialize::<VClock<u8>>(&bytes).unwrap(), clock);
+}
+
+#[test]
+fn test_merge_dots_keeps_maxima() {
+    let mut clock = VClock::from(Dot::new(1, 3));
+    clock.merge_dots(vec![
+        Dot::new(2, 5),
+        Dot::new(1, 2),
+        Dot::new(2, 1),
+        Dot::new(3, 4),
+        Dot::new(2, 5),
+    ]);
+
+    let expected: VClock<u8> = VClock::from_slice(&[(1, 3), (2, 5), (3, 4)]);
+    assert_eq!(clock, expected);
+}
+
+#[test]
+fn test_has_seen_and_is_next() {
+    let clock: VClock<u8> = VClock::from_slice(&[(1, 3)]);
+
+    assert!(clock.has_seen(&Dot::new(1, 2)));
+    assert!(clock.has_seen(&Dot::new(1, 3)));
+    assert!(!clock.is_next(&Dot::new(1, 3)));
+
+    assert!(!clock.has_seen(&Dot::new(1, 4)));
+    assert!(clock.is_next(&Dot::new(1, 4)));
+
+    assert!(!clock.has_seen(&Dot::new(1, 5)));
+    assert!(!clock.is_next(&Dot::new(1, 5)));
+
+    assert!(!clock.has_seen(&Dot::new(2, 1)));
+    assert!(clock.is_next(&Dot::new(2, 1)));
+}
+
+#[test]
+fn test_is_next_at_counter_bounds() {
+    let clock: VClock<u8> = VClock::from_slice(&[(1, u64::MAX), (2, u64::MAX - 1)]);
+
+    assert!(!clock.is_next(&Dot::new(1, u64::MAX)));
+    assert!(clock.is_next(&Dot::new(2, u64::MAX)));
+    assert!(!clock.is_next(&Dot::new(3, 0)));
+}
+
+#[test]
+fn test_diff_summary() {
+    let a: VClock<&str> = VClock::from_slice(&[("A", 3), ("B", 1), ("C", 4)]);
+    let b: VClock<&str> = VClock::from_slice(&[("A", 1), ("B", 2), ("C", 4)]);
+
+    assert_eq!(
+        a.diff_summary(&b),
+        "self leads on A by 2, behind on B by 1, equal on C; relation: concurrent"
+    );
+    assert_eq!(
+        b.diff_summary(&VClock::new()),
+        "self leads on A by 1, leads on B by 2, leads on C by 4; relation: ahead"
+    );
+    assert_eq!(
+        VClock::<&str>::new().diff_summary(&VClock::new()),
+        "relation: equal"
+    );
+}
+
+#[test]
+fn test_is_linear_extension_of() {
+    let base: VClock<u8> = VClock::from_slice(&[(1, 2), (2, 1)]);
+
+    let mut linear = base.clone();
+    linear.merge_dot(Dot::new(1, 5));
+    assert!(linear.is_linear_extension_of(&base));
+    assert!(base.is_linear_extension_of(&base));
+    assert!(!base.is_linear_extension_of(&linear));
+
+    let mut branch = base.clone();
+    branch.merge_dot(Dot::new(3, 1));
+    assert!(!branch.is_linear_extension_of(&base));
+
+    let concurrent = VClock::from(Dot::new(1, 5));
+    assert!(!concurrent.is_linear_extension_of(&base));
+}
+
+#[test]
+fn test_counter_histogram_and_skew() {
+    let clock: VClock<&str> = VClock::from_slice(&[("A", 10), ("B", 2), ("C", 5)]);
+
+    let histogram = clock.counter_histogram();
+    assert_eq!(histogram.get("A"), Some(&10));
+    assert_eq!(histogram.get("B"), Some(&2));
+    assert_eq!(histogram.get("C"), Some(&5));
+    assert_eq!(clock.skew(), 8);
+
+    assert_eq!(VClock::<&str>::new().skew(), 0);
+}
+
+#[test]
+fn test_merge_tracking_returns_advanced_actors() {
+    let mut clock: VClock<&str> = VClock::from_slice(&[("A", 1), ("B", 4), ("C", 2)]);
+    let other: VClock<&str> = VClock::from_slice(&[("A", 3), ("B", 4), ("C", 5)]);
+
+    assert_eq!(clock.merge_tracking(&other), vec!["A", "C"]);
+    assert_eq!(clock, other);
+    assert_eq!(clock.merge_tracking(&other), Vec::<&str>::new());
+}
+
+#[test]
+fn test_merge_dot_stores() {
+    let mut clock = VClock::new();
+
+    clock.merge_from(Dot::new(1, 2));
+    assert_eq!(clock, VClock::from(Dot::new(1, 2)));
+
+    clock.merge_from(vec![Dot::new(1, 1), Dot::new(2, 3)]);
+    assert_eq!(clock.get(&1), 2);
+    assert_eq!(clock.get(&2), 3);
+
+    clock.merge_from(DotRange::new(1, 3, 6));
+    assert_eq!(clock.get(&1), 6);
+
+    let other: VClock<u8> = VClock::from_slice(&[(2, 7), (3, 1)]);
+    clock.merge_from(other);
+    let expected: VClock<u8> = VClock::from_slice(&[(1, 6), (2, 7), (3, 1)]);
+    assert_eq!(clock, expected);
+}
+
+#[test]
+fn test_forget_is_empty() {
+    let clock: VClock<u8> = VClock::from_slice(&[(1, 2), (2, 3)]);
+
+    let dominating: VClock<u8> = VClock::from_slice(&[(1, 2), (2, 4)]);
+    assert!(clock.clone().forget_is_empty(&dominating));
+
+    let partial = VClock::from(Dot::new(1, 5));
+    let mut forgotten = clock;
+    assert!(!forgotten.forget_is_empty(&partial));
+    assert_eq!(forgotten, VClock::from(Dot::new(2, 3)));
+}
+
+#[test]
+fn test_range_yields_actors_in_range() {
+    let clock: VClock<u32> = vec![5, 10, 15, 19, 20, 25]
+        .into_iter()
+        .map(|actor| Dot::new(actor, 1))
+        .collect();
+
+    let actors: Vec<_> = clock.range(10..20).map(|dot| dot.actor).collect();
+    assert_eq!(actors, vec![10, 15, 19]);
+    assert_eq!(clock.range(30..).count(), 0);
+}
+
+#[test]
+fn test_try_witness_reports_dominating_dot() {
+    let mut clock = VClock::from(Dot::new(1, 3));
+
+    assert_eq!(clock.try_witness(1, 3), Err(Dot::new(1, 3)));
+    assert_eq!(clock.try_witness(1, 2), Err(Dot::new(1, 3)));
+    assert_eq!(clock, VClock::from(Dot::new(1, 3)));
+
+    assert_eq!(clock.try_witness(1, 5), Ok(()));
+    assert_eq!(clock.try_witness(2, 1), Ok(()));
+    let expected: VClock<u8> = VClock::from_slice(&[(1, 5), (2, 1)]);
+    assert_eq!(clock, expected);
+}
+
+#[test]
+fn test_compressed_bytes() {
+    let small: VClock<u64> = (0..100).map(|actor| Dot::new(actor * 3, 5)).collect();
+    let bytes = small.compressed_bytes();
+    assert!(bytes.len() < small.dots.len() * 16 / 4);
+    assert_eq!(VClock::from_compressed_bytes(&bytes), Some(small));
+
+    let large: VClock<u64> = VClock::from_slice(&[(u64::MAX - 1, u64::MAX), (2, 1 << 40)]);
+    assert_eq!(
+        VClock::from_compressed_bytes(&large.compressed_bytes()),
+        Some(large.clone())
+    );
+
+    // actors that don't fit the actor type, truncated input and trailing bytes are rejected
+    assert_eq!(
+        VClock::<u8>::from_compressed_bytes(&large.compressed_bytes()),
+        None
+    );
+    let bytes = large.compressed_bytes();
+    assert_eq!(
+        VClock::<u64>::from_compressed_bytes(&bytes[..bytes.len() - 1]),
+        None
+    );
+    let mut bytes = bytes;
+    bytes.push(0);
+    assert_eq!(VClock::<u64>::from_compressed_bytes(&bytes), None);
+}
+
+#[test]
+fn test_compressed_bytes_rejects_malformed_varints() {
+    // a clock with a single actor 0, followed by the encoding of its counter
+    let decode = |counter: &[u8]| {
+        let mut bytes = vec![1, 0];
+        bytes.extend_from_slice(counter);
+        VClock::<u64>::from_compressed_bytes(&bytes)
+    };
+
+    let mut max = vec![0xff; 9];
+    max.push(0x01);
+    assert_eq!(decode(&max), Some(VClock::from(Dot::new(0, u64::MAX))));
+
+    // payload bits past the 64th
+    let mut overflow = vec![0xff; 9];
+    overflow.push(0x02);
+    assert_eq!(decode(&overflow), None);
+    let mut overflow = vec![0x80; 9];
+    overflow.push(0x7f);
+    assert_eq!(decode(&overflow), None);
+
+    // more than 10 bytes
+    let mut overlong = vec![0x80; 10];
+    overlong.push(0x00);
+    assert_eq!(decode(&overlong), None);
+
+    // padded with a zero byte
+    assert_eq!(decode(&[0x05]), Some(VClock::from(Dot::new(0, 5))));
+    assert_eq!(decode(&[0x85, 0x00]), None);
+}
+
+#[test]
+fn test_to_labeled_json() {
+    let clock = VClock::from_slice(&[("a1f3", 3), ("07bc", 1)]);
+    let mut labels = BTreeMap::new();
+    labels.insert("a1f3", "alice".to_string());
+    labels.insert("07bc", "bob".to_string());
+    assert_eq!(
+        clock.to_labeled_json(&labels),
+        r#"{ "bob": 1, "alice": 3 }"#
+    );
+
+    labels.remove("07bc");
+    let json = clock.to_labeled_json(&labels);
+    let parsed: BTreeMap<String, u64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.get("\"07bc\""), Some(&1));
+    assert_eq!(parsed.get("alice"), Some(&3));
+}
+
+#[test]
+fn test_seen_by_quorum() {
+    let clocks = vec![
+        VClock::from_slice(&[("A", 3), ("B", 1)]),
+        VClock::from_slice(&[("A", 2)]),
+        VClock::from_slice(&[("A", 1), ("B", 4)]),
+    ];
+    let dot = Dot::new("A", 2);
+    assert!(VClock::seen_by_quorum(&dot, &clocks, 2));
+    assert!(!VClock::seen_by_quorum(&dot, &clocks, 3));
+    assert!(VClock::seen_by_quorum(&Dot::new("C", 1), &clocks, 0));
+}
+
+#[test]
+fn test_into_iter_moves_dots_out() {
+    let clock = VClock::from_slice(&[("B", 2), ("A", 4)]);
+    let expected = clock.to_sorted_vec();
+    let dots: Vec<Dot<&str>> = clock.into_iter().collect();
+    assert_eq!(dots, expected);
+}
+
+#[test]
+fn test_debug_assert_comparable() {
+    let a = VClock::from_slice(&[("A", 1)]);
+    let b = VClock::from_slice(&[("A", 2), ("B", 1)]);
+    a.debug_assert_comparable(&b);
+    b.debug_assert_comparable(&a);
+    a.debug_assert_comparable(&a);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "concurrent clocks")]
+fn test_debug_assert_comparable_panics_on_concurrent() {
+    let a = VClock::from_slice(&[("A", 2)]);
+    let b = VClock::from_slice(&[("B", 1)]);
+    a.debug_assert_comparable(&b);
+}
+
+#[test]
+fn test_merged_and_add_assign() {
+    let a = VClock::from_slice(&[("A", 3), ("B", 1)]);
+    let b = VClock::from_slice(&[("A", 1), ("B", 2), ("C", 1)]);
+    let expected = VClock::from_slice(&[("A", 3), ("B", 2), ("C", 1)]);
+
+    let c = a.clone().merged(b.clone());
+    assert_eq!(c, expected);
+
+    let mut d = a;
+    d += &b;
+    assert_eq!(d, expected);
+}
+
+#[test]
+fn test_collect_clocks_into_lub() {
+    let replicas = vec![
+        VClock::from_slice(&[("A", 3), ("B", 1)]),
+        VClock::from_slice(&[("B", 4)]),
+        VClock::from_slice(&[("A", 1), ("C", 2)]),
+    ];
+    let frontier: VClock<&str> = replicas.iter().cloned().collect();
+
+    let expected = replicas.into_iter().fold(VClock::new(), |mut acc, clock| {
+        acc.merge(clock);
+        acc
+    });
+    assert_eq!(frontier, expected);
+    assert_eq!(
+        frontier,
+        VClock::from_slice(&[("A", 3), ("B", 4), ("C", 2)])
+    );
+}
+
+#[test]
+fn test_dotted_vclock_gaps() {
+    let mut clock = DottedVClock::new();
+    clock.apply(Dot::new("A", 1));
+    clock.apply(Dot::new("A", 3));
+    clock.apply(Dot::new("B", 1));
+
+    assert_eq!(clock.gaps(&"A"), vec![2]);
+    assert!(clock.gaps(&"B").is_empty());
+    assert!(clock.gaps(&"C").is_empty());
+    assert!(clock.has_seen(&Dot::new("A", 3)));
+    assert!(!clock.has_seen(&Dot::new("A", 2)));
+    assert_eq!(
+        clock.contiguous(),
+        &VClock::from_slice(&[("A", 1), ("B", 1)])
+    );
+    assert_eq!(clock.frontier(), VClock::from_slice(&[("A", 3), ("B", 1)]));
+
+    clock.apply(Dot::new("A", 2));
+    assert!(clock.gaps(&"A").is_empty());
+    assert_eq!(clock.contiguous(), &clock.frontier());
+}
+
+quickcheck! {
+    fn prop_merge_if_newer_matches_merge(a: VClock<u8>, b: VClock<u8>) -> bool {
+        let mut merged = a.clone();
+        merged.merge(b.clone());
+
+        let mut fast = a.clone();
+        let newer = fast.merge_if_newer(&b);
+        fast == merged && newer == (merged != a)
+    }
+}
+
+#[test]
+fn test_merge_if_newer_leaves_dominating_clock_untouched() {
+    let mut a = VClock::from_slice(&[("A", 3), ("B", 2), ("C", 1)]);
+    let b = VClock::from_slice(&[("A", 1), ("C", 1)]);
+    assert!(!a.merge_if_newer(&b));
+    assert_eq!(a, VClock::from_slice(&[("A", 3), ("B", 2), ("C", 1)]));
+
+    let c = VClock::from_slice(&[("B", 3), ("D", 1)]);
+    assert!(a.merge_if_newer(&c));
+    assert_eq!(
+        a,
+        VClock::from_slice(&[("A", 3), ("B", 3), ("C", 1), ("D", 1)])
+    );
+}
+
+#[test]
+fn test_dots_iter_in_actor_order() {
+    let clock = VClock::from_slice(&[("C", 1), ("A", 3), ("B", 2)]);
+    let dots: Vec<_> = clock.dots_iter().collect();
+    assert_eq!(
+        dots,
+        vec![Dot::new("A", 3), Dot::new("B", 2), Dot::new("C", 1)]
+    );
+    assert_eq!(clock.dots_iter().filter(|dot| dot.counter > 1).count(), 2);
+}
+
+#[test]
+fn test_set_to_drops_actors_unlike_merge() {
+    let a = VClock::from_slice(&[("A", 3), ("B", 2)]);
+    let snapshot = VClock::from_slice(&[("A", 1), ("C", 4)]);
+
+    let mut merged = a.clone();
+    merged.merge(snapshot.clone());
+    assert_eq!(merged, VClock::from_slice(&[("A", 3), ("B", 2), ("C", 4)]));
+
+    let mut set = a;
+    set.set_to(&snapshot);
+    assert_eq!(set, snapshot);
+    assert_eq!(set.get(&"B"), 0);
+}
+
+#[test]
+fn test_glb_all() {
+    let a = VClock::from_slice(&[("A", 4), ("B", 2), ("C", 7)]);
+    let b = VClock::from_slice(&[("A", 3), ("B", 5), ("C", 6)]);
+    let c = VClock::from_slice(&[("A", 5), ("C", 2)]);
+    let d = VClock::from_slice(&[("A", 1), ("B", 1), ("C", 9)]);
+
+    assert_eq!(VClock::<&str>::glb_all(vec![]), VClock::new());
+    assert_eq!(
+        VClock::glb_all(vec![a.clone(), b.clone(), c.clone()]),
+        VClock::from_slice(&[("A", 3), ("C", 2)])
+    );
+    assert_eq!(
+        VClock::glb_all(vec![a, b, c, d]),
+        VClock::from_slice(&[("A", 1), ("C", 2)])
+    );
+}
+
+#[test]
+fn test_glb_all_stops_once_empty() {
+    let clocks = vec![
+        VClock::from_slice(&[("A", 1)]),
+        VClock::from_slice(&[("B", 1)]),
+        VClock::from_slice(&[("A", 1)]),
+        VClock::from_slice(&[("A", 1)]),
+    ];
+    let mut consumed = 0;
+    let glb = VClock::glb_all(clocks.into_iter().inspect(|_| consumed += 1));
+    assert!(glb.is_empty());
+    assert_eq!(consumed, 3);
+}
+
+#[test]
+fn test_inc_and_apply_inc() {
+    let mut clock = VClock::from_slice(&[("A", 2)]);
+    assert_eq!(clock.inc("A"), Dot::new("A", 3));
+    assert_eq!(clock.inc("A"), Dot::new("A", 3));
+
+    assert_eq!(clock.apply_inc("A"), Dot::new("A", 3));
+    assert_eq!(clock.apply_inc("A"), Dot::new("A", 4));
+    assert_eq!(clock.apply_inc("B"), Dot::new("B", 1));
+    assert_eq!(clock, VClock::from_slice(&[("A", 4), ("B", 1)]));
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(VClock::<&str>::new().to_string(), "<>");
+    assert_eq!(VClock::from_slice(&[("A", 3)]).to_string(), "<A:3>");
+    assert_eq!(
+        VClock::from_slice(&[("B", 7), ("A", 3)]).to_string(),
+        "<A:3, B:7>"
+    );
+}
+
+#[test]
+fn test_owned_dots_round_trip_through_from_iterator() {
+    let clock = VClock::from_slice(&[("C", 1), ("A", 3), ("B", 2)]);
+
+    let borrowed: Vec<Dot<&str>> = clock.dots_iter().collect();
+    assert_eq!(borrowed.iter().cloned().collect::<VClock<_>>(), clock);
+
+    let owned: Vec<Dot<&str>> = clock.clone().into_iter().collect();
+    assert_eq!(owned, borrowed);
+    assert_eq!(owned.into_iter().collect::<VClock<_>>(), clock);
+}
+
+#[test]
+fn test_add_dot() {
+    let mut clock = VClock::from_slice(&[("A", 3)]);
+    clock += Dot::new("A", 2);
+    assert_eq!(clock, VClock::from_slice(&[("A", 3)]));
+
+    clock += Dot::new("A", 4);
+    assert_eq!(clock.get(&"A"), 4);
+
+    let grown = clock.clone() + Dot::new("B", 1);
+    assert_eq!(grown, VClock::from_slice(&[("A", 4), ("B", 1)]));
+    assert_eq!(clock.clone() + Dot::new("A", 1), clock);
+}
+
+#[test]
+fn test_merge_and_collect_keep_the_larger_counter() {
+    let mut a = VClock::from_slice(&[("A", 5)]);
+    a.merge(VClock::from_slice(&[("A", 3)]));
+    assert_eq!(a.get(&"A"), 5);
+
+    let collected: VClock<_> = vec![Dot::new("A", 5), Dot::new("A", 3)]
+        .into_iter()
+        .collect();
+    assert_eq!(collected, a);
+}
+
+/// The implementation of `partial_cmp` before it was made a single pass over both clocks.
+fn naive_partial_cmp(a: &VClock<u8>, b: &VClock<u8>) -> Option<Ordering> {
+    if a == b {
+        Some(Ordering::Equal)
+    } else if b.dots.iter().all(|(w, c)| a.get(w) >= *c) {
+        Some(Ordering::Greater)
+    } else if a.dots.iter().all(|(w, c)| b.get(w) >= *c) {
+        Some(Ordering::Less)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_partial_cmp_matches_naive_implementation() {
+    fn prop(a: VClock<u8>, b: VClock<u8>, shared: VClock<u8>) -> bool {
+        // comparable pairs are rare among random clocks, build some from a shared history
+        let mut ahead_a = shared.clone();
+        ahead_a.merge(a.clone());
+        let pairs = [(&a, &b), (&shared, &ahead_a), (&ahead_a, &shared), (&a, &a)];
+        pairs
+            .iter()
+            .all(|(x, y)| x.partial_cmp(y) == naive_partial_cmp(x, y))
+    }
+    quickcheck::QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(VClock<u8>, VClock<u8>, VClock<u8>) -> bool);
+}
+
+#[test]
+fn test_len() {
+    let mut clock = VClock::new();
+    assert_eq!(clock.len(), 0);
+    assert!(clock.is_empty());
+
+    clock.apply(Dot::new("A", 1));
+    clock.apply(Dot::new("B", 3));
+    clock.apply(Dot::new("A", 2));
+    assert_eq!(clock.len(), 2);
+    assert!(!clock.is_empty());
+}
+
+#[test]
+fn test_clear() {
+    let mut clock = VClock::from_slice(&[("A", 2), ("B", 1)]);
+    clock.clear();
+    assert_eq!(clock, VClock::new());
+
+    clock.apply(Dot::new("C", 1));
+    assert_eq!(clock, VClock::from_slice(&[("C", 1)]));
+}
+
+#[test]
+fn test_retain() {
+    let mut clock = VClock::from_slice(&[("A", 9), ("B", 4), ("C", 5), ("D", 1)]);
+    clock.retain(|_, counter| counter >= 5);
+    assert_eq!(clock, VClock::from_slice(&[("A", 9), ("C", 5)]));
+
+    clock.retain(|actor, _| *actor != "A");
+    assert_eq!(clock, VClock::from_slice(&[("C", 5)]));
+}
+
+#[test]
+fn test_max_counter_and_total_events() {
+    let empty = VClock::<&str>::new();
+    assert_eq!(empty.max_counter(), 0);
+    assert_eq!(empty.total_events(), 0);
+
+    let single = VClock::from_slice(&[("A", 4)]);
+    assert_eq!(single.max_counter(), 4);
+    assert_eq!(single.total_events(), 4);
+
+    let multi = VClock::from_slice(&[("A", 4), ("B", 9), ("C", 2)]);
+    assert_eq!(multi.max_counter(), 9);
+    assert_eq!(multi.total_events(), 15);
+
+    let huge = VClock::from_slice(&[("A", u64::MAX), ("B", 1)]);
+    assert_eq!(huge.total_events(), u64::MAX);
+}
+
+#[test]
+fn test_actors() {
+    let clock = VClock::from_slice(&[("C", 1), ("A", 5), ("B", 2)]);
+    assert_eq!(clock.actors().collect::<Vec<_>>(), vec![&"A", &"B", &"C"]);
+}
+
+#[test]
+fn test_contains_and_remove_actor() {
+    let mut clock = VClock::from_slice(&[("A", 3), ("B", 1)]);
+    assert!(clock.contains_actor(&"A"));
+    assert!(!clock.contains_actor(&"C"));
+
+    assert_eq!(clock.remove_actor(&"A"), Some(3));
+    assert!(!clock.contains_actor(&"A"));
+    assert_eq!(clock.remove_actor(&"A"), None);
+    assert_eq!(clock.remove_actor(&"C"), None);
+    assert_eq!(clock, VClock::from_slice(&[("B", 1)]));
+}
+
+#[test]
+fn test_relation_and_dominates() {
+    let a = VClock::from_slice(&[("A", 2), ("B", 1)]);
+    let ahead = VClock::from_slice(&[("A", 3), ("B", 1)]);
+    let concurrent = VClock::from_slice(&[("A", 1), ("B", 4)]);
+
+    assert_eq!(a.relation(&a.clone()), Relation::Equal);
+    assert_eq!(ahead.relation(&a), Relation::Dominates);
+    assert_eq!(a.relation(&ahead), Relation::Dominated);
+    assert_eq!(a.relation(&concurrent), Relation::Concurrent);
+
+    assert!(ahead.dominates(&a));
+    assert!(!a.dominates(&ahead));
+    assert!(!a.dominates(&a));
+    assert!(!a.dominates(&concurrent) && !concurrent.dominates(&a));
+}
+
+#[test]
+fn test_inc_at_max_counter() {
+    let mut clock = VClock::from_slice(&[("A", u64::MAX - 1)]);
+    assert_eq!(clock.try_inc("A"), Ok(Dot::new("A", u64::MAX)));
+    clock.apply(clock.inc("A"));
+    assert_eq!(clock.get(&"A"), u64::MAX);
+
+    assert_eq!(clock.try_inc("A"), Err(Error::CounterOverflow));
+    assert_eq!(clock.inc("A"), Dot::new("A", u64::MAX));
+    clock.apply(clock.inc("A"));
+    assert_eq!(clock.get(&"A"), u64::MAX);
+}
+
+#[test]
+fn test_diff() {
+    let a = VClock::from_slice(&[("A", 3), ("B", 1), ("C", 2)]);
+    let b = VClock::from_slice(&[("A", 3), ("B", 4), ("D", 7)]);
+
+    assert_eq!(a.diff(&b), VClock::from_slice(&[("C", 2)]));
+    assert_eq!(&b - &a, VClock::from_slice(&[("B", 4), ("D", 7)]));
+    assert_eq!(a.diff(&VClock::new()), a);
+    assert!(a.diff(&a).is_empty());
+
+    let mut caught_up = b.clone();
+    caught_up.merge(a.diff(&b));
+    assert_eq!(caught_up, VClock::lub(&a, &b));
+}